@@ -0,0 +1,108 @@
+//! Throughput of the batch conversions compared to converting the colors one by one
+//!
+//! Run with `cargo bench`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use colliberator::batch::*;
+use colliberator::*;
+
+/// The number of pixels in a 4K image
+const PIXELS: usize = 3840 * 2160;
+
+/// Runs the function a few times and prints the best throughput in megapixels per second
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    let mut best = Duration::MAX;
+    for _ in 0..5 {
+        let start = Instant::now();
+        f();
+        best = best.min(start.elapsed());
+    }
+    let throughput = PIXELS as f64 / best.as_secs_f64() / 1e6;
+    println!("{:<32} {:>10.1} Mpx/s", name, throughput);
+}
+
+fn main() {
+    let srgb24: Vec<SRGB24Color> = (0..PIXELS)
+        .map(|i| {
+            (
+                (i % 256) as u8,
+                (i / 256 % 256) as u8,
+                (i / 65536 % 256) as u8,
+            )
+                .into()
+        })
+        .collect();
+    let srgb48: Vec<RGBColor<u16, space::SRGBSpace>> =
+        srgb24.iter().map(|c| c.conv::<u16>()).collect();
+    let srgb: Vec<SRGBColor> = srgb24.iter().map(|c| c.conv::<f32>()).collect();
+    let mut linear = vec![LinRGBColor::default(); PIXELS];
+    let mut out24 = vec![SRGB24Color::default(); PIXELS];
+    let mut hsv = vec![StdHSVColor::default(); PIXELS];
+
+    bench("u8 sRGB decode, per color", || {
+        for (l, s) in linear.iter_mut().zip(&srgb24) {
+            *l = s.to_linear();
+        }
+        black_box(&linear);
+    });
+    bench("u8 sRGB decode, per color LUT", || {
+        for (l, s) in linear.iter_mut().zip(&srgb24) {
+            *l = LinRGBColor::from(*s);
+        }
+        black_box(&linear);
+    });
+    bench("u8 sRGB decode, batch", || {
+        decode_srgb24(black_box(&srgb24), &mut linear);
+        black_box(&linear);
+    });
+    bench("u16 sRGB decode, per color", || {
+        for (l, s) in linear.iter_mut().zip(&srgb48) {
+            *l = s.to_linear();
+        }
+        black_box(&linear);
+    });
+    bench("u16 sRGB decode, batch", || {
+        decode_srgb48(black_box(&srgb48), &mut linear);
+        black_box(&linear);
+    });
+    bench("u8 sRGB encode, per color", || {
+        for (o, l) in out24.iter_mut().zip(&linear) {
+            *o = SRGB24Color::from_linear(*l);
+        }
+        black_box(&out24);
+    });
+    bench("u8 sRGB encode, per color LUT", || {
+        for (o, l) in out24.iter_mut().zip(&linear) {
+            *o = SRGB24Color::from(*l);
+        }
+        black_box(&out24);
+    });
+    bench("u8 sRGB encode, batch", || {
+        encode_srgb24(black_box(&linear), &mut out24);
+        black_box(&out24);
+    });
+    bench("RGB to HSV, per color", || {
+        for (h, s) in hsv.iter_mut().zip(&srgb) {
+            *h = s.hsv();
+        }
+        black_box(&hsv);
+    });
+    bench("RGB to HSV, batch", || {
+        rgb_to_hsv(black_box(&srgb), &mut hsv);
+        black_box(&hsv);
+    });
+
+    let channels = SRGB24Color::as_channels(&srgb24);
+    let mut floats = vec![0.0; channels.len()];
+    let mut bytes = vec![0; channels.len()];
+    bench("u8 to f32, batch", || {
+        u8_to_f32(black_box(channels), &mut floats);
+        black_box(&floats);
+    });
+    bench("f32 to u8, batch", || {
+        f32_to_u8(black_box(&floats), &mut bytes);
+        black_box(&bytes);
+    });
+}
@@ -0,0 +1,187 @@
+use crate::*;
+
+/// Trait for the common color adjustments
+///
+/// The amounts given to the adjustment functions are fractions between 0 and 1, telling how
+/// far the color is moved towards the extreme of the adjustment, eg. `lighten(0.1)` moves the
+/// color 10% of the way towards white. Amounts outside of this range are clamped.
+///
+/// The adjustments are done in the following spaces:
+/// * `lighten` and `darken` mix the color with white or black in the linear space.
+/// * `saturate`, `desaturate` and `shift_hue` change the HSV channels in the color's own space.
+/// * `invert` inverts the RGB channels in the color's own space.
+/// * `grayscale` returns the grey with the same relative luminance.
+///
+/// The exception to this are the perceptual `OklchColor`s, which are adjusted by changing their
+/// lightness, chroma and hue channels.
+///
+/// Alpha colors are adjusted by adjusting their inner color, the alpha channel is kept as is.
+pub trait ColorOps: Sized {
+    /// The color type without the alpha channel
+    type Opaque;
+
+    /// Mixes this color with white by the given amount
+    fn lighten(self, amount: f32) -> Self;
+
+    /// Mixes this color with black by the given amount
+    fn darken(self, amount: f32) -> Self;
+
+    /// Moves the saturation of this color towards full saturation by the given amount
+    fn saturate(self, amount: f32) -> Self;
+
+    /// Moves the saturation of this color towards grey by the given amount
+    fn desaturate(self, amount: f32) -> Self;
+
+    /// Rotates the hue of this color by the given angle
+    fn shift_hue<A: Angle + Channel>(self, angle: A) -> Self;
+
+    /// Returns the inverse of this color
+    fn invert(self) -> Self;
+
+    /// Returns the grey color with the same relative luminance as this color
+    fn grayscale(self) -> Self;
+
+    /// Returns this color with the given alpha channel
+    ///
+    /// If this color already has an alpha channel, it is replaced.
+    fn with_alpha<A: Channel>(self, alpha: A) -> Alpha<Self::Opaque, A>;
+}
+
+/// Moves `value` towards `target` by the fraction `amount`
+#[inline]
+fn approach(value: f32, target: f32, amount: f32) -> f32 {
+    value + (target - value) * Channel::clamp(amount)
+}
+
+impl<T: Channel, S: RGBSpace> ColorOps for RGBColor<T, S> {
+    type Opaque = Self;
+
+    fn lighten(self, amount: f32) -> Self {
+        let white = LinRGBColor::new(1.0, 1.0, 1.0);
+        let lin = self.conv::<f32>().decode();
+        lin.blend(&white, 1.0 - Channel::clamp(amount))
+            .encode::<S>()
+            .conv()
+    }
+
+    fn darken(self, amount: f32) -> Self {
+        let lin = self.conv::<f32>().decode();
+        (lin * (1.0 - Channel::clamp(amount))).encode::<S>().conv()
+    }
+
+    fn saturate(self, amount: f32) -> Self {
+        self.conv::<f32>()
+            .hsv::<Deg<f32>>()
+            .saturate(amount)
+            .rgb()
+            .conv()
+    }
+
+    fn desaturate(self, amount: f32) -> Self {
+        self.conv::<f32>()
+            .hsv::<Deg<f32>>()
+            .desaturate(amount)
+            .rgb()
+            .conv()
+    }
+
+    fn shift_hue<A: Angle + Channel>(self, angle: A) -> Self {
+        self.conv::<f32>()
+            .hsv::<Deg<f32>>()
+            .shift_hue(angle)
+            .rgb()
+            .conv()
+    }
+
+    fn invert(self) -> Self {
+        self.conv::<f32>().map(|c| 1.0 - c).conv()
+    }
+
+    fn grayscale(self) -> Self {
+        let lum = self.conv::<f32>().decode().relative_luminance();
+        LinRGBColor::new(lum, lum, lum).encode::<S>().conv()
+    }
+
+    fn with_alpha<A: Channel>(self, alpha: A) -> Alpha<Self, A> {
+        Alpha::new(self, alpha)
+    }
+}
+
+impl<H: Channel, T: Channel, S: RGBSpace> ColorOps for HSVColor<H, T, S> {
+    type Opaque = Self;
+
+    fn lighten(self, amount: f32) -> Self {
+        self.conv::<H, f32>()
+            .rgb()
+            .lighten(amount)
+            .hsv::<H>()
+            .conv()
+    }
+
+    fn darken(self, amount: f32) -> Self {
+        self.conv::<H, f32>().rgb().darken(amount).hsv::<H>().conv()
+    }
+
+    fn saturate(self, amount: f32) -> Self {
+        let (h, s, v) = self.conv::<H, f32>().tuple();
+        HSVColor::<H, f32, S>::new(h, approach(s, 1.0, amount), v).conv()
+    }
+
+    fn desaturate(self, amount: f32) -> Self {
+        let (h, s, v) = self.conv::<H, f32>().tuple();
+        HSVColor::<H, f32, S>::new(h, approach(s, 0.0, amount), v).conv()
+    }
+
+    fn shift_hue<A: Angle + Channel>(self, angle: A) -> Self {
+        let (h, s, v) = self.conv::<Deg<f32>, f32>().tuple();
+        HSVColor::<Deg<f32>, f32, S>::new(h + angle.conv(), s, v).conv()
+    }
+
+    fn invert(self) -> Self {
+        self.conv::<H, f32>().rgb().invert().hsv::<H>().conv()
+    }
+
+    fn grayscale(self) -> Self {
+        self.conv::<H, f32>().rgb().grayscale().hsv::<H>().conv()
+    }
+
+    fn with_alpha<A: Channel>(self, alpha: A) -> Alpha<Self, A> {
+        Alpha::new(self, alpha)
+    }
+}
+
+impl<C: ColorOps, A: Channel> ColorOps for Alpha<C, A> {
+    type Opaque = C;
+
+    fn lighten(self, amount: f32) -> Self {
+        Alpha::new(self.color.lighten(amount), self.alpha)
+    }
+
+    fn darken(self, amount: f32) -> Self {
+        Alpha::new(self.color.darken(amount), self.alpha)
+    }
+
+    fn saturate(self, amount: f32) -> Self {
+        Alpha::new(self.color.saturate(amount), self.alpha)
+    }
+
+    fn desaturate(self, amount: f32) -> Self {
+        Alpha::new(self.color.desaturate(amount), self.alpha)
+    }
+
+    fn shift_hue<B: Angle + Channel>(self, angle: B) -> Self {
+        Alpha::new(self.color.shift_hue(angle), self.alpha)
+    }
+
+    fn invert(self) -> Self {
+        Alpha::new(self.color.invert(), self.alpha)
+    }
+
+    fn grayscale(self) -> Self {
+        Alpha::new(self.color.grayscale(), self.alpha)
+    }
+
+    fn with_alpha<B: Channel>(self, alpha: B) -> Alpha<C, B> {
+        Alpha::new(self.color, alpha)
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
+use num_traits::ops::saturating::{SaturatingAdd, SaturatingMul, SaturatingSub};
 use num_traits::Float;
 
 use crate::*;
@@ -9,7 +11,11 @@ use crate::*;
 /// Alpha of 1 means the color is fully opaque, and alpha of 0 means it's fully transparent.
 ///
 /// This uses a straight alpha, not a premultiplied alpha.
+///
+/// The alpha channel is stored after the color. An RGB color with an alpha channel of the same
+/// type has the same memory layout as `[T; 4]`.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+#[repr(C)]
 pub struct Alpha<C, A> {
     pub color: C,
     pub alpha: A,
@@ -26,6 +32,24 @@ impl<C, A: Channel> Alpha<C, A> {
             alpha: alpha.clamp(),
         }
     }
+
+    /// Creates a new alpha channel, or returns an error if the alpha is out of range
+    ///
+    /// Only the alpha is checked, so the color should be created with it's own `try_new`.
+    pub fn try_new(color: C, alpha: A) -> Result<Self, ChannelRangeError> {
+        Ok(Alpha {
+            color,
+            alpha: check_range(alpha, "alpha")?,
+        })
+    }
+}
+
+impl<C, A> Alpha<C, A> {
+    /// Creates a new alpha channel without clamping it
+    #[inline]
+    pub fn new_unchecked(color: C, alpha: A) -> Self {
+        Alpha { color, alpha }
+    }
 }
 
 impl<C: Color, A: Channel> Color for Alpha<C, A> {
@@ -166,6 +190,124 @@ impl<T: Channel + Clone, C: From<[T; 3]>> From<&[T; 4]> for Alpha<C, T> {
     }
 }
 
+macro_rules! impl_alpha_ops {
+    ( $( $trait:ident, $fun:ident, $as_trait:ident, $as_fun:ident );* ) => { $(
+        impl<C, A> $trait for Alpha<C, A>
+        where
+            C: $trait<Output = C>,
+            A: Channel + $trait<Output = A>,
+        {
+            type Output = Self;
+            fn $fun(self, rhs: Self) -> Self::Output {
+                Alpha::new(self.color.$fun(rhs.color), self.alpha.$fun(rhs.alpha))
+            }
+        }
+
+        impl<C, A> $as_trait for Alpha<C, A>
+        where
+            C: $trait<Output = C> + Copy,
+            A: Channel + $trait<Output = A> + Copy,
+        {
+            fn $as_fun(&mut self, rhs: Self) {
+                *self = $trait::$fun(*self, rhs);
+            }
+        }
+    )* };
+}
+
+impl_alpha_ops!(
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign
+);
+
+impl<T> Mul<T> for Alpha<RGBColor<T, LinearSpace>, T>
+where
+    T: Channel + Mul<Output = T> + Clone,
+{
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self::Output {
+        Alpha::new(self.color * rhs.clone(), self.alpha * rhs)
+    }
+}
+
+impl<T> Div<T> for Alpha<RGBColor<T, LinearSpace>, T>
+where
+    T: Channel + Div<Output = T> + Clone,
+{
+    type Output = Self;
+    fn div(self, rhs: T) -> Self::Output {
+        Alpha::new(self.color / rhs.clone(), self.alpha / rhs)
+    }
+}
+
+impl<T> MulAssign<T> for Alpha<RGBColor<T, LinearSpace>, T>
+where
+    T: Channel + Mul<Output = T> + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> DivAssign<T> for Alpha<RGBColor<T, LinearSpace>, T>
+where
+    T: Channel + Div<Output = T> + Copy,
+{
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+impl<C: Neg<Output = C>, A: Neg<Output = A>> Neg for Alpha<C, A> {
+    type Output = Self;
+
+    /// Negates all the channels of this color, including the alpha channel
+    ///
+    /// Like with the opaque colors, the result is not clamped, because clamping would make every
+    /// color transparent black. It's meant to be used in further arithmetic, which clamps.
+    fn neg(self) -> Self::Output {
+        Alpha {
+            color: -self.color,
+            alpha: -self.alpha,
+        }
+    }
+}
+
+impl<T, A> Alpha<RGBColor<T, LinearSpace>, A>
+where
+    T: Channel + SaturatingAdd + SaturatingSub + SaturatingMul,
+    A: Channel + SaturatingAdd + SaturatingSub + SaturatingMul,
+{
+    /// Adds the channels of the colors together, saturating at the numeric bounds
+    #[inline]
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Alpha::new(
+            self.color.saturating_add(rhs.color),
+            self.alpha.saturating_add(&rhs.alpha),
+        )
+    }
+
+    /// Subtracts the channels of the colors, saturating at the numeric bounds
+    #[inline]
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Alpha::new(
+            self.color.saturating_sub(rhs.color),
+            self.alpha.saturating_sub(&rhs.alpha),
+        )
+    }
+
+    /// Multiplies the channels of the colors, saturating at the numeric bounds
+    #[inline]
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        Alpha::new(
+            self.color.saturating_mul(rhs.color),
+            self.alpha.saturating_mul(&rhs.alpha),
+        )
+    }
+}
+
 impl<C: fmt::UpperHex> fmt::UpperHex for Alpha<C, u8> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:X}{:02X}", self.color, self.alpha)
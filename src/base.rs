@@ -1,22 +1,76 @@
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+use crate::*;
+
+#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
 /// A basic color of the rainbow
 pub enum BaseColor {
+    #[default]
     Black,
     Grey,
     White,
     Red,
+    Orange,
     Yellow,
     Green,
     Cyan,
     Blue,
+    Purple,
     Magenta,
+    Pink,
+    Brown,
 }
 
-impl Default for BaseColor {
-    fn default() -> Self {
-        BaseColor::Black
+impl BaseColor {
+    /// All the base colors
+    pub const ALL: [BaseColor; 13] = [
+        BaseColor::Black,
+        BaseColor::Grey,
+        BaseColor::White,
+        BaseColor::Red,
+        BaseColor::Orange,
+        BaseColor::Yellow,
+        BaseColor::Green,
+        BaseColor::Cyan,
+        BaseColor::Blue,
+        BaseColor::Purple,
+        BaseColor::Magenta,
+        BaseColor::Pink,
+        BaseColor::Brown,
+    ];
+
+    /// Returns an iterator over all the base colors
+    pub fn all() -> impl Iterator<Item = BaseColor> {
+        Self::ALL.iter().copied()
+    }
+
+    /// Returns the hue of this color in the HSV space
+    ///
+    /// Returns `None` for the greyscale colors black, grey and white.
+    pub fn hue(self) -> Option<Deg<f32>> {
+        use self::BaseColor::*;
+
+        match self {
+            Black | Grey | White => None,
+            Red => Some(Deg(0.0)),
+            Orange | Brown => Some(Deg(30.0)),
+            Yellow => Some(Deg(60.0)),
+            Green => Some(Deg(120.0)),
+            Cyan => Some(Deg(180.0)),
+            Blue => Some(Deg(240.0)),
+            Purple => Some(Deg(270.0)),
+            Magenta => Some(Deg(300.0)),
+            Pink => Some(Deg(348.0)),
+        }
+    }
+
+    /// Returns the perceived lightness of this color between 0 and 1
+    ///
+    /// This is the lightness of the color in the Oklab space.
+    pub fn lightness(self) -> f32 {
+        SRGBColor::from(self).to_linear().oklab().l
     }
 }
 
@@ -32,12 +86,57 @@ impl fmt::Display for BaseColor {
                 Grey => "grey",
                 White => "white",
                 Red => "red",
+                Orange => "orange",
                 Yellow => "yellow",
                 Green => "green",
                 Cyan => "cyan",
                 Blue => "blue",
+                Purple => "purple",
                 Magenta => "magenta",
+                Pink => "pink",
+                Brown => "brown",
             }
         )
     }
 }
+
+/// An error from parsing an unknown color name into a `BaseColor`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseBaseColorError;
+
+impl fmt::Display for ParseBaseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown base color name")
+    }
+}
+
+impl Error for ParseBaseColorError {}
+
+impl FromStr for BaseColor {
+    type Err = ParseBaseColorError;
+
+    /// Parses a base color from it's name, ignoring case
+    ///
+    /// Accepts the names given by the `Display` implementation and the aliases `gray`,
+    /// `violet`, `fuchsia` and `aqua`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::BaseColor::*;
+
+        Ok(match s.trim().to_ascii_lowercase().as_str() {
+            "black" => Black,
+            "grey" | "gray" => Grey,
+            "white" => White,
+            "red" => Red,
+            "orange" => Orange,
+            "yellow" => Yellow,
+            "green" => Green,
+            "cyan" | "aqua" => Cyan,
+            "blue" => Blue,
+            "purple" | "violet" => Purple,
+            "magenta" | "fuchsia" => Magenta,
+            "pink" => Pink,
+            "brown" => Brown,
+            _ => return Err(ParseBaseColorError),
+        })
+    }
+}
@@ -0,0 +1,124 @@
+//! Conversions of whole pixel buffers
+//!
+//! These do the same conversions as the methods of the colors, and the results are the same as
+//! with the per-color methods. The integer sRGB conversions use lookup tables instead of `powf`,
+//! and the channel and RGB to HSV conversions are simple loops over `f32`s without the generic
+//! channel conversions, so they're faster than converting the colors one by one.
+//!
+//! The floating point sRGB conversions and the HSV to RGB conversion still do the same work for
+//! every color as the per-color methods. They're here for completeness and aren't any faster.
+//!
+//! All the functions write the results into the destination slice.
+//!
+//! # Panics
+//! All the functions panic if the source and the destination have different lengths.
+
+use crate::space::{decode_table_u16, decode_table_u8, encode_tables};
+use crate::*;
+
+fn check_lengths<T, U>(src: &[T], dst: &[U]) {
+    assert_eq!(
+        src.len(),
+        dst.len(),
+        "source and destination have different lengths"
+    );
+}
+
+/// Converts 8-bit channels into floating point channels between 0 and 1
+pub fn u8_to_f32(src: &[u8], dst: &mut [f32]) {
+    check_lengths(src, dst);
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = s as f32 / 255.0;
+    }
+}
+
+/// Converts floating point channels into 8-bit channels, clamping and rounding them
+pub fn f32_to_u8(src: &[f32], dst: &mut [u8]) {
+    check_lengths(src, dst);
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = (s.clamp(0.0, 1.0) * 255.0).round() as u8;
+    }
+}
+
+/// Decodes 8-bit sRGB colors into the linear space
+pub fn decode_srgb24(src: &[SRGB24Color], dst: &mut [LinRGBColor]) {
+    check_lengths(src, dst);
+    let src = SRGB24Color::as_channels(src);
+    let dst = LinRGBColor::as_channels_mut(dst);
+    let table = decode_table_u8();
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = table[s as usize];
+    }
+}
+
+/// Decodes 16-bit sRGB colors into the linear space
+pub fn decode_srgb48(src: &[RGBColor<u16, SRGBSpace>], dst: &mut [LinRGBColor]) {
+    check_lengths(src, dst);
+    let src = RGBColor::as_channels(src);
+    let dst = LinRGBColor::as_channels_mut(dst);
+    let table = decode_table_u16();
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = table[s as usize];
+    }
+}
+
+/// Decodes floating point sRGB colors into the linear space
+///
+/// Same as calling `RGBColor::std_decode` for every color, and no faster.
+pub fn decode_srgb(src: &[SRGBColor], dst: &mut [LinRGBColor]) {
+    check_lengths(src, dst);
+    let src = SRGBColor::as_channels(src);
+    let dst = LinRGBColor::as_channels_mut(dst);
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = Channel::clamp(std_gamma_decode(s));
+    }
+}
+
+/// Encodes linear colors into floating point sRGB colors
+///
+/// Same as calling `RGBColor::std_encode` for every color, and no faster.
+pub fn encode_srgb(src: &[LinRGBColor], dst: &mut [SRGBColor]) {
+    check_lengths(src, dst);
+    let src = LinRGBColor::as_channels(src);
+    let dst = SRGBColor::as_channels_mut(dst);
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = Channel::clamp(std_gamma_encode(s));
+    }
+}
+
+/// Encodes linear colors into 8-bit sRGB colors
+pub fn encode_srgb24(src: &[LinRGBColor], dst: &mut [SRGB24Color]) {
+    check_lengths(src, dst);
+    let src = LinRGBColor::as_channels(src);
+    let dst = SRGB24Color::as_channels_mut(dst);
+    let tables = encode_tables();
+    for (d, &s) in dst.iter_mut().zip(src) {
+        *d = tables.encode(s);
+    }
+}
+
+/// Converts RGB colors into HSV colors
+///
+/// Same as calling `RGBColor::hsv` for every color.
+pub fn rgb_to_hsv<S>(src: &[RGBColor<f32, S>], dst: &mut [HSVColor<Deg<f32>, f32, S>]) {
+    check_lengths(src, dst);
+    for (d, s) in dst.iter_mut().zip(src) {
+        let (r, g, b) = (
+            s.r.clamp(0.0, 1.0),
+            s.g.clamp(0.0, 1.0),
+            s.b.clamp(0.0, 1.0),
+        );
+        let (hue, saturation, value) = rgb::rgb_to_hsv(r, g, b);
+        *d = HSVColor::new(hue.conv::<Deg<f32>>(), saturation, value);
+    }
+}
+
+/// Converts HSV colors into RGB colors
+///
+/// Same as calling `HSVColor::rgb` for every color, and no faster.
+pub fn hsv_to_rgb<S>(src: &[HSVColor<Deg<f32>, f32, S>], dst: &mut [RGBColor<f32, S>]) {
+    check_lengths(src, dst);
+    for (d, s) in dst.iter_mut().zip(src) {
+        *d = s.rgb();
+    }
+}
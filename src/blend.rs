@@ -26,6 +26,6 @@ impl<T: Channel + Float> Blend<RGBColor<T, LinearSpace>> for RGBColor<T, LinearS
     /// this function is undefined behaviour.
     #[inline]
     fn blend(&self, foreground: &Self, ratio: T) -> Self {
-        *self * ratio + *foreground * (T::max_value() - ratio)
+        *self * ratio + *foreground * (T::ch_max() - ratio)
     }
 }
@@ -0,0 +1,120 @@
+use std::error::Error;
+use std::fmt;
+use std::mem::{align_of, size_of};
+use std::slice;
+
+use crate::*;
+
+/// Error returned when a slice of channels can't be cast into a slice of colors
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CastError {
+    /// The length of the slice of channels
+    pub len: usize,
+    /// The number of channels in one color
+    pub channels: usize,
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "slice of {} channels can't be split into colors of {} channels",
+            self.len, self.channels
+        )
+    }
+}
+
+impl Error for CastError {}
+
+/// Checks that `C` is laid out as `N` consecutive `T`s
+///
+/// This always holds for the `#[repr(C)]` colors, but it's checked to keep the casts honest.
+fn check_layout<T, C, const N: usize>(len: usize) -> Result<usize, CastError> {
+    assert_eq!(size_of::<C>(), size_of::<[T; N]>());
+    assert_eq!(align_of::<C>(), align_of::<T>());
+
+    if len % N != 0 {
+        return Err(CastError { len, channels: N });
+    }
+    Ok(len / N)
+}
+
+macro_rules! impl_casts {
+    ( $ty:ty, $n:expr, $what:expr ) => {
+        impl<T, S> $ty {
+            #[doc = concat!("Reinterprets a slice of channels as a slice of ", $what)]
+            ///
+            /// The channels are not clamped. Returns an error if the length of the slice isn't
+            /// a multiple of the number of channels in a color.
+            pub fn from_channels(channels: &[T]) -> Result<&[Self], CastError> {
+                let len = check_layout::<T, Self, $n>(channels.len())?;
+                // the layout was checked above, and the lifetime is the same
+                Ok(unsafe { slice::from_raw_parts(channels.as_ptr() as *const Self, len) })
+            }
+
+            #[doc = concat!("Reinterprets a mutable slice of channels as a slice of ", $what)]
+            ///
+            /// See `from_channels`.
+            pub fn from_channels_mut(channels: &mut [T]) -> Result<&mut [Self], CastError> {
+                let len = check_layout::<T, Self, $n>(channels.len())?;
+                // the layout was checked above, and the lifetime is the same
+                Ok(unsafe { slice::from_raw_parts_mut(channels.as_mut_ptr() as *mut Self, len) })
+            }
+
+            #[doc = concat!("Reinterprets a slice of ", $what, " as a slice of their channels")]
+            pub fn as_channels(colors: &[Self]) -> &[T] {
+                let len = colors.len() * $n;
+                // the colors are always laid out as their channels
+                unsafe { slice::from_raw_parts(colors.as_ptr() as *const T, len) }
+            }
+
+            #[doc = concat!("Reinterprets a mutable slice of ", $what, " as a slice of their channels")]
+            pub fn as_channels_mut(colors: &mut [Self]) -> &mut [T] {
+                let len = colors.len() * $n;
+                // the colors are always laid out as their channels
+                unsafe { slice::from_raw_parts_mut(colors.as_mut_ptr() as *mut T, len) }
+            }
+        }
+    };
+}
+
+impl_casts!(RGBColor<T, S>, 3, "RGB colors");
+impl_casts!(Alpha<RGBColor<T, S>, T>, 4, "RGBA colors");
+impl_casts!(Bgr<T, S>, 3, "BGR colors");
+impl_casts!(Bgra<T, S>, 4, "BGRA colors");
+impl_casts!(Argb<T, S>, 4, "ARGB colors");
+impl_casts!(Abgr<T, S>, 4, "ABGR colors");
+
+// The colors only contain their channels, so they're plain old data if the channels are.
+#[cfg(feature = "bytemuck")]
+mod pod {
+    use bytemuck::{Pod, Zeroable};
+
+    use crate::*;
+
+    unsafe impl<T: Zeroable, S> Zeroable for RGBColor<T, S> {}
+    unsafe impl<T: Pod, S: 'static> Pod for RGBColor<T, S> {}
+
+    // the alpha must be of the same type as the channels, otherwise there could be padding
+    unsafe impl<T: Zeroable, S> Zeroable for Alpha<RGBColor<T, S>, T> {}
+    unsafe impl<T: Pod, S: 'static> Pod for Alpha<RGBColor<T, S>, T> {}
+
+    unsafe impl<T: Zeroable, S> Zeroable for Bgr<T, S> {}
+    unsafe impl<T: Pod, S: 'static> Pod for Bgr<T, S> {}
+    unsafe impl<T: Zeroable, S> Zeroable for Bgra<T, S> {}
+    unsafe impl<T: Pod, S: 'static> Pod for Bgra<T, S> {}
+    unsafe impl<T: Zeroable, S> Zeroable for Argb<T, S> {}
+    unsafe impl<T: Pod, S: 'static> Pod for Argb<T, S> {}
+    unsafe impl<T: Zeroable, S> Zeroable for Abgr<T, S> {}
+    unsafe impl<T: Pod, S: 'static> Pod for Abgr<T, S> {}
+
+    // the packed colors are transparent wrappers of integers
+    macro_rules! impl_pod_packed {
+        ( $( $type:ty ),* ) => { $(
+            unsafe impl Zeroable for $type {}
+            unsafe impl Pod for $type {}
+        )* };
+    }
+
+    impl_pod_packed!(RGB565, RGB555, RGBA4444, RGB10A2, R11G11B10F, BGRA8, ARGB8, ABGR8);
+}
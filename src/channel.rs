@@ -1,8 +1,11 @@
 pub mod angle;
 
-use num_traits::NumCast;
+use std::error::Error;
+use std::fmt;
 
-use crate::{cuwf, cuwtf};
+use num_traits::{NumCast, ToPrimitive};
+
+use crate::{cuwd, cuwf, cuwtd, cuwtf};
 
 /// A trait for color channels
 pub trait Channel: Sized + PartialOrd + NumCast {
@@ -11,6 +14,12 @@ pub trait Channel: Sized + PartialOrd + NumCast {
     /// If false the channel has a floating point value.
     const INTEGER: bool;
 
+    /// The number of significant bits in this channel's values
+    ///
+    /// Conversions between channels with at most 24 bits are done with `f32`, and with `f64`
+    /// otherwise, so that they don't lose precision.
+    const PRECISION: u32 = 24;
+
     /// The maximum value for this channel, inclusive
     ///
     /// With integers it's usually the max value, with floats it's one.
@@ -30,8 +39,13 @@ pub trait Channel: Sized + PartialOrd + NumCast {
     ///
     /// The values will be made to fit into their range.
     fn conv<T: Channel>(self) -> T {
-        let float = cuwtf(self.clamp()) / cuwtf(Self::ch_max()) * cuwtf(T::ch_max());
-        cuwf(if T::INTEGER { float.round() } else { float })
+        if Self::PRECISION.max(T::PRECISION) <= 24 {
+            let float = cuwtf(self.clamp()) / cuwtf(Self::ch_max()) * cuwtf(T::ch_max());
+            cuwf(if T::INTEGER { float.round() } else { float })
+        } else {
+            let float = cuwtd(self.clamp()) / cuwtd(Self::ch_max()) * cuwtd(T::ch_max());
+            cuwd(if T::INTEGER { float.round() } else { float })
+        }
     }
 
     /// Return whether this value is inside the channel's allowed range
@@ -51,12 +65,52 @@ pub trait Channel: Sized + PartialOrd + NumCast {
     }
 }
 
+/// Error returned by the checked constructors when a channel is out of it's range
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ChannelRangeError {
+    /// The name of the channel, like "red" or "alpha"
+    pub channel: &'static str,
+    /// The value of the channel
+    ///
+    /// Angles are given in their own unit, not as a fraction of a full revolution.
+    pub value: f64,
+}
+
+impl fmt::Display for ChannelRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "the {} channel is out of range: {}",
+            self.channel, self.value
+        )
+    }
+}
+
+impl Error for ChannelRangeError {}
+
+/// Returns the value if it's in the channel's range, and an error naming the channel otherwise
+pub(crate) fn check_range<T: Channel>(
+    value: T,
+    channel: &'static str,
+) -> Result<T, ChannelRangeError> {
+    if value.in_range() {
+        Ok(value)
+    } else {
+        Err(ChannelRangeError {
+            channel,
+            value: value.to_f64().unwrap_or(f64::NAN),
+        })
+    }
+}
+
+/// Implements unsigned normalized channels, where the maximum value is 1.0
 macro_rules! impl_uint_channels {
     ( $( $type:ty ),* ) => { $(
         impl Channel for $type {
             const INTEGER: bool = true;
-            fn ch_max() -> Self { <$type>::max_value() }
-            fn ch_mid() -> Self { <$type>::max_value() / 2 }
+            const PRECISION: u32 = <$type>::BITS;
+            fn ch_max() -> Self { <$type>::MAX }
+            fn ch_mid() -> Self { <$type>::MAX / 2 }
             fn ch_zero() -> Self { 0 }
         }
     )* };
@@ -64,6 +118,25 @@ macro_rules! impl_uint_channels {
 
 impl_uint_channels!(u8, u16, u32);
 
+/// Implements channels for signed integers, where zero is 0.0 and the maximum value is 1.0
+///
+/// These aren't signed normalized (snorm) channels, the values are unsigned normalized like with
+/// the unsigned integers and the sign bit is unused. The colors don't have negative channels, so
+/// the negative values are out of range and clamped to zero.
+macro_rules! impl_int_channels {
+    ( $( $type:ty ),* ) => { $(
+        impl Channel for $type {
+            const INTEGER: bool = true;
+            const PRECISION: u32 = <$type>::BITS - 1;
+            fn ch_max() -> Self { <$type>::MAX }
+            fn ch_mid() -> Self { <$type>::MAX / 2 }
+            fn ch_zero() -> Self { 0 }
+        }
+    )* };
+}
+
+impl_int_channels!(i8, i16, i32);
+
 impl Channel for f32 {
     const INTEGER: bool = false;
     fn ch_max() -> Self {
@@ -76,3 +149,85 @@ impl Channel for f32 {
         0.0
     }
 }
+
+impl Channel for f64 {
+    const INTEGER: bool = false;
+    const PRECISION: u32 = f64::MANTISSA_DIGITS;
+    fn ch_max() -> Self {
+        1.0
+    }
+    fn ch_mid() -> Self {
+        0.5
+    }
+    fn ch_zero() -> Self {
+        0.0
+    }
+}
+
+#[cfg(feature = "half")]
+impl Channel for half::f16 {
+    const INTEGER: bool = false;
+    const PRECISION: u32 = half::f16::MANTISSA_DIGITS;
+    fn ch_max() -> Self {
+        half::f16::ONE
+    }
+    fn ch_mid() -> Self {
+        half::f16::from_f32(0.5)
+    }
+    fn ch_zero() -> Self {
+        half::f16::ZERO
+    }
+}
+
+/// An unsigned fixed-point channel with `FRAC` fractional bits
+///
+/// Unlike with the integer channels, where the maximum value is 1.0, the value of 1.0 is
+/// `1 << FRAC`. `FRAC` must be smaller than the number of bits in `T`, otherwise using the
+/// channel fails to compile.
+///
+/// For example `Fixed<u16, 12>` stores 1.0 as 4096.
+#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Fixed<T, const FRAC: u32>(pub T);
+
+macro_rules! impl_fixed_channels {
+    ( $( $type:ty ),* ) => { $(
+        impl<const FRAC: u32> Fixed<$type, FRAC> {
+            /// The value of 1.0, checked at compile time to fit into the type
+            const ONE: $type = {
+                assert!(FRAC < <$type>::BITS, "too many fractional bits for the type");
+                1 << FRAC
+            };
+        }
+
+        impl<const FRAC: u32> Channel for Fixed<$type, FRAC> {
+            const INTEGER: bool = true;
+            const PRECISION: u32 = <$type>::BITS;
+            fn ch_max() -> Self { Fixed(Self::ONE) }
+            fn ch_mid() -> Self { Fixed(Self::ONE >> 1) }
+            fn ch_zero() -> Self { Fixed(0) }
+        }
+
+        impl<const FRAC: u32> NumCast for Fixed<$type, FRAC> {
+            fn from<U: ToPrimitive>(n: U) -> Option<Self> {
+                <$type as NumCast>::from(n).map(Fixed)
+            }
+        }
+    )* };
+}
+
+impl_fixed_channels!(u8, u16, u32);
+
+impl<T: ToPrimitive, const FRAC: u32> ToPrimitive for Fixed<T, FRAC> {
+    fn to_i64(&self) -> Option<i64> {
+        self.0.to_i64()
+    }
+    fn to_u64(&self) -> Option<u64> {
+        self.0.to_u64()
+    }
+    fn to_f32(&self) -> Option<f32> {
+        self.0.to_f32()
+    }
+    fn to_f64(&self) -> Option<f64> {
+        self.0.to_f64()
+    }
+}
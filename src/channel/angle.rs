@@ -3,12 +3,12 @@
 //! All operations done for these types immediately wrap around, so it is impossible to
 //! create a value out of bounds with them
 
-use std::f32::consts::PI as PI32;
+use std::f64::consts::PI;
 use std::ops::*;
 
-use num_traits::{NumCast, NumOps, ToPrimitive};
+use num_traits::{Float, NumCast, NumOps, ToPrimitive};
 
-use crate::{cuwf, cuwtf, Channel};
+use crate::{cuwd, cuwf, cuwtd, cuwtf, Channel};
 
 /// A trait for all angle types
 ///
@@ -16,7 +16,7 @@ use crate::{cuwf, cuwtf, Channel};
 /// the value of full revolution (360° in degrees, 2π in radians).
 pub trait Angle: Sized {
     /// The inner type of this angle
-    type Inner: PartialOrd + From<Self> + Into<Self> + NumCast + NumOps;
+    type Inner: Channel + From<Self> + Into<Self> + NumOps;
 
     /// Tells whether this angle's inner value is an integer type
     ///
@@ -42,6 +42,121 @@ pub trait Angle: Sized {
             a.into()
         }
     }
+
+    /// Returns this angle as a fraction of a full revolution, between 0 and 1
+    fn turns(self) -> f64 {
+        let turns = cuwtd(Into::<Self::Inner>::into(self.wrap()))
+            / cuwtd(Into::<Self::Inner>::into(Self::full_angle()));
+        // a NaN angle is treated as zero, so that it doesn't spread into the colors
+        if turns.is_nan() {
+            0.0
+        } else {
+            turns
+        }
+    }
+
+    /// Creates an angle from a fraction of a full revolution, wrapping it into the normal range
+    fn from_turns(turns: f64) -> Self {
+        let full = cuwtd(Into::<Self::Inner>::into(Self::full_angle()));
+        let value = turns.rem_euclid(1.0) * full;
+        let value = if Self::INTEGER { value.round() } else { value };
+        Into::<Self>::into(cuwd::<Self::Inner>(value)).wrap()
+    }
+
+    /// Returns the length of the shortest arc between this angle and the other one
+    ///
+    /// The result is at most half of a full revolution.
+    fn difference(self, other: Self) -> Self {
+        let delta = (other.turns() - self.turns()).abs();
+        Self::from_turns(delta.min(1.0 - delta))
+    }
+
+    /// Returns the signed length of the shortest arc from this angle to the other one, as a
+    /// fraction of a full revolution
+    ///
+    /// The result is between -0.5 and 0.5, and it's positive if the arc goes in the positive
+    /// direction.
+    fn signed_difference(self, other: Self) -> f64 {
+        shortest_arc(self.turns(), other.turns())
+    }
+
+    /// Interpolates linearly from this angle to the other one along the shortest arc
+    ///
+    /// `t` of 0 gives this angle and 1 gives the other angle.
+    fn lerp(self, other: Self, t: f32) -> Self {
+        let (from, to) = (self.turns(), other.turns());
+        Self::from_turns(from + shortest_arc(from, to) * t as f64)
+    }
+}
+
+/// Returns the signed shortest arc between two angles in revolutions
+fn shortest_arc(from: f64, to: f64) -> f64 {
+    let delta = (to - from).rem_euclid(1.0);
+    if delta > 0.5 {
+        delta - 1.0
+    } else {
+        delta
+    }
+}
+
+/// The sum of weighted unit vectors of angles
+#[derive(Debug, Copy, Clone, Default)]
+pub(crate) struct Resultant {
+    sin: f64,
+    cos: f64,
+    weight: f64,
+}
+
+impl Resultant {
+    /// Sums the angles, ignoring the ones with a zero, negative or NaN weight
+    pub(crate) fn new<A: Angle, I: IntoIterator<Item = (A, f32)>>(angles: I) -> Self {
+        let mut sum = Resultant::default();
+        for (angle, weight) in angles {
+            if weight.is_nan() || weight <= 0.0 {
+                continue;
+            }
+            let (sin, cos) = (angle.turns() * 2.0 * PI).sin_cos();
+            sum.sin += sin * weight as f64;
+            sum.cos += cos * weight as f64;
+            sum.weight += weight as f64;
+        }
+        sum
+    }
+
+    /// Returns the sum of the weights
+    pub(crate) fn weight(&self) -> f64 {
+        self.weight
+    }
+
+    /// Returns the mean resultant length, between 0 and 1
+    pub(crate) fn length(&self) -> f64 {
+        self.sin.hypot(self.cos) / self.weight
+    }
+
+    /// Returns the direction of the resultant, or `None` if it has no length
+    pub(crate) fn mean<A: Angle>(&self) -> Option<A> {
+        if self.weight <= 0.0 || self.length() < 1e-9 {
+            return None;
+        }
+        Some(A::from_turns(self.sin.atan2(self.cos) / (2.0 * PI)))
+    }
+}
+
+/// Returns the circular mean of the angles
+///
+/// The angles are averaged as unit vectors, so that for example the mean of 350° and 10° is 0°.
+/// Returns `None` if there are no angles or if they cancel each other out, like two opposite
+/// angles do.
+pub fn mean_angle<A: Angle, I: IntoIterator<Item = A>>(angles: I) -> Option<A> {
+    weighted_mean_angle(angles.into_iter().map(|angle| (angle, 1.0)))
+}
+
+/// Returns the weighted circular mean of the angles
+///
+/// Like `mean_angle`, but every angle has a weight. Angles with a zero, negative or NaN weight
+/// are ignored.
+pub fn weighted_mean_angle<A: Angle, I: IntoIterator<Item = (A, f32)>>(angles: I) -> Option<A> {
+    Resultant::new(angles).mean()
 }
 
 /// A wrapper type for angles in degrees
@@ -50,9 +165,10 @@ pub struct Deg<T>(pub T);
 
 /// A wrapper type for angles in radians
 #[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq)]
-pub struct Rad(pub f32);
+pub struct Rad<T = f32>(pub T);
 
 /// A wrapper type for angles in revolutions
+#[derive(Debug, Default, Copy, Clone, PartialOrd, Ord, PartialEq, Eq)]
 pub struct Rev<T>(pub T);
 
 macro_rules! impl_deg_angles {
@@ -66,19 +182,21 @@ macro_rules! impl_deg_angles {
     )* };
 }
 
-impl_deg_angles!(Deg; i16, true; i32, true; f32, false);
+impl_deg_angles!(Deg; i16, true; i32, true; f32, false; f64, false);
 
-impl Angle for Rad {
-    type Inner = f32;
-    const INTEGER: bool = false;
-    fn full_angle() -> Self {
-        Self(2.0 * PI32)
-    }
-    fn zero_angle() -> Self {
-        Self(0.0)
-    }
+macro_rules! impl_rad_angles {
+    ( $( $type:ty ),* ) => { $(
+        impl Angle for Rad<$type> {
+            type Inner = $type;
+            const INTEGER: bool = false;
+            fn full_angle() -> Self { Self(2.0 * PI as $type) }
+            fn zero_angle() -> Self { Self(0.0) }
+        }
+    )* };
 }
 
+impl_rad_angles!(f32, f64);
+
 macro_rules! impl_int_rev_angles {
     ( $struct_name:ident, $( $type:ty ),* ) => { $(
         impl Angle for $struct_name<$type> {
@@ -93,19 +211,22 @@ macro_rules! impl_int_rev_angles {
 
 impl_int_rev_angles!(Rev, u8, u16, u32);
 
-impl Angle for Rev<f32> {
-    type Inner = f32;
-    const INTEGER: bool = false;
-    fn full_angle() -> Self {
-        Self(1.0)
-    }
-    fn zero_angle() -> Self {
-        Self(0.0)
-    }
+macro_rules! impl_float_rev_angles {
+    ( $( $type:ty ),* ) => { $(
+        impl Angle for Rev<$type> {
+            type Inner = $type;
+            const INTEGER: bool = false;
+            fn full_angle() -> Self { Self(1.0) }
+            fn zero_angle() -> Self { Self(0.0) }
+        }
+    )* };
 }
 
+impl_float_rev_angles!(f32, f64);
+
 impl<T: Angle + NumCast + PartialOrd> Channel for T {
     const INTEGER: bool = T::INTEGER;
+    const PRECISION: u32 = T::Inner::PRECISION;
 
     fn ch_max() -> Self {
         T::full_angle()
@@ -127,9 +248,9 @@ impl<T: NumCast> NumCast for Deg<T> {
     }
 }
 
-impl NumCast for Rad {
-    fn from<T: ToPrimitive>(n: T) -> Option<Self> {
-        n.to_f32().map(Self)
+impl<T: NumCast> NumCast for Rad<T> {
+    fn from<U: ToPrimitive>(n: U) -> Option<Self> {
+        T::from(n).map(Self)
     }
 }
 
@@ -147,13 +268,11 @@ macro_rules! impl_to_prim_fns {
         fn to_i8(&self) -> Option<i8>       { self.0.to_i8() }
         fn to_i16(&self) -> Option<i16>     { self.0.to_i16() }
         fn to_i32(&self) -> Option<i32>     { self.0.to_i32() }
-        #[cfg(has_i128)]
         fn to_i128(&self) -> Option<i128>   { self.0.to_i128() }
         fn to_usize(&self) -> Option<usize> { self.0.to_usize() }
         fn to_u8(&self) -> Option<u8>       { self.0.to_u8() }
         fn to_u16(&self) -> Option<u16>     { self.0.to_u16() }
         fn to_u32(&self) -> Option<u32>     { self.0.to_u32() }
-        #[cfg(has_i128)]
         fn to_u128(&self) -> Option<u128>   { self.0.to_u128() }
         fn to_f32(&self) -> Option<f32>     { self.0.to_f32() }
         fn to_f64(&self) -> Option<f64>     { self.0.to_f64() }
@@ -164,7 +283,7 @@ impl<T: ToPrimitive> ToPrimitive for Deg<T> {
     impl_to_prim_fns!();
 }
 
-impl ToPrimitive for Rad {
+impl<T: ToPrimitive> ToPrimitive for Rad<T> {
     impl_to_prim_fns!();
 }
 
@@ -184,21 +303,9 @@ macro_rules! generic_newtype_from_impls {
     )* };
 }
 
-generic_newtype_from_impls!(Deg, i16, i32, f32);
-
-impl From<f32> for Rad {
-    fn from(n: f32) -> Self {
-        Self(n)
-    }
-}
-
-impl From<Rad> for f32 {
-    fn from(angle: Rad) -> Self {
-        angle.0
-    }
-}
-
-generic_newtype_from_impls!(Rev, u8, u16, u32, f32);
+generic_newtype_from_impls!(Deg, i16, i32, f32, f64);
+generic_newtype_from_impls!(Rad, f32, f64);
+generic_newtype_from_impls!(Rev, u8, u16, u32, f32, f64);
 
 macro_rules! impl_newtype_ops {
     ( $struct_name:ident;
@@ -223,29 +330,6 @@ macro_rules! impl_newtype_ops {
     )* };
 }
 
-macro_rules! impl_rad_ops {
-    ( $struct_name:ident;
-      $( $trait:ident, $fun:ident, $as_trait:ident, $as_fun:ident );*
-    ) => { $(
-        impl $trait for $struct_name
-            where Self: Angle
-        {
-            type Output = Self;
-            fn $fun(self, rhs: Self) -> Self {
-                (Self((self.0).$fun(rhs.0))).wrap()
-            }
-        }
-
-        impl $as_trait for $struct_name
-            where Self: Angle
-        {
-            fn $as_fun(&mut self, rhs: Self) {
-                (self.0).$as_fun(rhs.0);
-            }
-        }
-    )* };
-}
-
 impl_newtype_ops!(Deg;
     Add, add, AddAssign, add_assign;
     Sub, sub, SubAssign, sub_assign;
@@ -254,7 +338,7 @@ impl_newtype_ops!(Deg;
     Rem, rem, RemAssign, rem_assign
 );
 
-impl_rad_ops!(Rad;
+impl_newtype_ops!(Rad;
     Add, add, AddAssign, add_assign;
     Sub, sub, SubAssign, sub_assign;
     Mul, mul, MulAssign, mul_assign;
@@ -269,3 +353,48 @@ impl_newtype_ops!(Rev;
     Div, div, DivAssign, div_assign;
     Rem, rem, RemAssign, rem_assign
 );
+
+impl<T: Float> Rad<T> {
+    /// Returns the angle whose sine is the given value
+    pub fn asin(sin: T) -> Self {
+        Rad(sin.asin())
+    }
+
+    /// Returns the angle whose cosine is the given value
+    pub fn acos(cos: T) -> Self {
+        Rad(cos.acos())
+    }
+
+    /// Returns the angle of the point `(x, y)` from the positive x axis
+    ///
+    /// Unlike `f32::atan2`, the angle is wrapped between 0 and 2π.
+    pub fn atan2(y: T, x: T) -> Self {
+        let full = T::from(2.0 * PI).unwrap();
+        let angle = y.atan2(x);
+        if angle < T::zero() {
+            Rad(angle + full)
+        } else {
+            Rad(angle)
+        }
+    }
+
+    /// Returns the sine of this angle
+    pub fn sin(self) -> T {
+        self.0.sin()
+    }
+
+    /// Returns the cosine of this angle
+    pub fn cos(self) -> T {
+        self.0.cos()
+    }
+
+    /// Returns the tangent of this angle
+    pub fn tan(self) -> T {
+        self.0.tan()
+    }
+
+    /// Returns both the sine and the cosine of this angle
+    pub fn sin_cos(self) -> (T, T) {
+        self.0.sin_cos()
+    }
+}
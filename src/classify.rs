@@ -0,0 +1,283 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::*;
+
+/// A range of hues around a center hue
+///
+/// Hues at the center fully belong to the range, and the membership falls linearly to zero
+/// at `margin` degrees away from the center.
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
+pub struct HueRange {
+    pub center: Deg<f32>,
+    pub margin: Deg<f32>,
+}
+
+impl HueRange {
+    /// Creates a new hue range from the center and margin in degrees
+    pub fn new(center: f32, margin: f32) -> Self {
+        HueRange {
+            center: Deg(center).wrap(),
+            margin: Deg(margin.abs()),
+        }
+    }
+
+    /// Returns how much the hue belongs to this range, between 0 and 1
+    pub fn membership(&self, hue: Deg<f32>) -> f32 {
+        let dist = (hue.wrap().0 - self.center.0).abs();
+        let dist = dist.min(360.0 - dist);
+        if dist >= self.margin.0 {
+            0.0
+        } else {
+            1.0 - dist / self.margin.0
+        }
+    }
+}
+
+/// A color category of a `Classifier`
+///
+/// The lightness and chroma are in the Oklch space. A color belongs to the category if its
+/// lightness and chroma are inside the ranges, and its hue is inside the hue range if there's
+/// one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Category<L> {
+    pub label: L,
+    pub hue: Option<HueRange>,
+    pub lightness: RangeInclusive<f32>,
+    pub chroma: RangeInclusive<f32>,
+}
+
+impl<L> Category<L> {
+    /// Creates a new category without a hue, matching all colors in the given ranges
+    pub fn new(label: L, lightness: RangeInclusive<f32>, chroma: RangeInclusive<f32>) -> Self {
+        Category {
+            label,
+            hue: None,
+            lightness,
+            chroma,
+        }
+    }
+
+    /// Sets the hue range of this category
+    pub fn with_hue(self, center: f32, margin: f32) -> Self {
+        Category {
+            hue: Some(HueRange::new(center, margin)),
+            ..self
+        }
+    }
+
+    /// Returns how much the color belongs to this category, between 0 and 1
+    pub fn membership(&self, color: &OklchColor<Deg<f32>, f32>) -> f32 {
+        if !self.lightness.contains(&color.l) || !self.chroma.contains(&color.c) {
+            return 0.0;
+        }
+        self.hue.map_or(1.0, |hue| hue.membership(color.h))
+    }
+
+    /// Returns how far the color is from belonging to this category
+    ///
+    /// The distance is zero for the colors that belong to the category. Otherwise it's the sum of
+    /// how far the lightness and chroma are outside their ranges and how far the hue is outside
+    /// the hue range as a fraction of a full circle.
+    pub fn distance(&self, color: &OklchColor<Deg<f32>, f32>) -> f32 {
+        let outside =
+            |range: &RangeInclusive<f32>, x: f32| (range.start() - x).max(x - range.end()).max(0.0);
+        let hue = self.hue.map_or(0.0, |hue| {
+            let dist = (color.h.wrap().0 - hue.center.0).abs();
+            (dist.min(360.0 - dist) - hue.margin.0).max(0.0) / 360.0
+        });
+        outside(&self.lightness, color.l) + outside(&self.chroma, color.c) + hue
+    }
+}
+
+/// A color classifier that names the most prominent shades of colors
+///
+/// The classifier has a list of categories, and a color is classified by how much it belongs
+/// to each of them.
+///
+/// The default classifier uses the `BaseColor`s as the categories. Custom categories can be
+/// added or parsed from a string, see the `FromStr` implementation for the format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Classifier<L = BaseColor> {
+    categories: Vec<Category<L>>,
+}
+
+impl<L> Classifier<L> {
+    /// Creates a classifier without any categories
+    pub fn new() -> Self {
+        Classifier {
+            categories: Vec::new(),
+        }
+    }
+
+    /// Adds a category to this classifier
+    pub fn add(&mut self, category: Category<L>) {
+        self.categories.push(category);
+    }
+
+    /// Returns this classifier with the given category added
+    pub fn with_category(mut self, category: Category<L>) -> Self {
+        self.add(category);
+        self
+    }
+
+    /// Returns the categories of this classifier
+    pub fn categories(&self) -> &[Category<L>] {
+        &self.categories
+    }
+
+    /// Classifies the color's most prominent shades
+    ///
+    /// Returns the labels of the categories the color belongs to with the amount it belongs to
+    /// them. The amounts sum up to 1 and they're sorted from the largest to the smallest.
+    ///
+    /// If the color doesn't belong to any category, the nearest category by
+    /// `Category::distance` is returned with the amount 1. Only a classifier without any
+    /// categories returns an empty vector.
+    pub fn classify<C: ToLinear>(&self, color: &C) -> Vec<(L, f32)>
+    where
+        L: Clone,
+    {
+        let color = color.to_linear().oklch::<Deg<f32>>();
+
+        let mut shades: Vec<(L, f32)> = self
+            .categories
+            .iter()
+            .map(|category| (category.label.clone(), category.membership(&color)))
+            .filter(|(_, amount)| *amount > 0.0)
+            .collect();
+
+        if shades.is_empty() {
+            return self
+                .categories
+                .iter()
+                .min_by(|a, b| a.distance(&color).total_cmp(&b.distance(&color)))
+                .map(|category| (category.label.clone(), 1.0))
+                .into_iter()
+                .collect();
+        }
+
+        let sum: f32 = shades.iter().map(|(_, amount)| amount).sum();
+
+        // sort and normalize
+        shades.sort_by(|(_, amount), (_, amount2)| amount2.partial_cmp(amount).unwrap());
+        for (_, amount) in shades.iter_mut() {
+            *amount /= sum;
+        }
+
+        shades
+    }
+}
+
+impl Default for Classifier {
+    /// Creates a classifier with the `BaseColor`s as it's categories
+    fn default() -> Self {
+        use self::BaseColor::*;
+
+        // these values have been picked by what gives nice results with the example palette
+        // the hue centers are the Oklch hues of the typical colors of the category, and the
+        // ranges overlap so that every color belongs to at least one category
+        const ANY: RangeInclusive<f32> = 0.0..=f32::INFINITY;
+        const COLORFUL: RangeInclusive<f32> = 0.04..=f32::INFINITY;
+        const GREYSCALE: RangeInclusive<f32> = 0.0..=0.06;
+
+        let c = Category::new;
+
+        Classifier::new()
+            .with_category(c(Black, 0.0..=0.3, ANY))
+            .with_category(c(Grey, 0.3..=0.9, GREYSCALE))
+            .with_category(c(White, 0.9..=1.0, GREYSCALE))
+            .with_category(c(Red, 0.3..=1.0, COLORFUL).with_hue(27.0, 30.0))
+            .with_category(c(Orange, 0.6..=1.0, COLORFUL).with_hue(55.0, 25.0))
+            .with_category(c(Brown, 0.25..=0.6, COLORFUL).with_hue(55.0, 40.0))
+            .with_category(c(Yellow, 0.6..=1.0, COLORFUL).with_hue(105.0, 30.0))
+            .with_category(c(Green, 0.3..=1.0, COLORFUL).with_hue(142.0, 50.0))
+            .with_category(c(Cyan, 0.3..=1.0, COLORFUL).with_hue(200.0, 35.0))
+            .with_category(c(Blue, 0.25..=1.0, COLORFUL).with_hue(264.0, 40.0))
+            .with_category(c(Purple, 0.3..=1.0, COLORFUL).with_hue(300.0, 30.0))
+            .with_category(c(Magenta, 0.3..=1.0, COLORFUL).with_hue(330.0, 30.0))
+            .with_category(c(Pink, 0.65..=1.0, COLORFUL).with_hue(350.0, 30.0))
+    }
+}
+
+/// An error from parsing `Classifier` category definitions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseClassifierError {
+    /// The line of the erroneous definition, starting from 1
+    pub line: usize,
+    /// The definition field that couldn't be parsed
+    pub field: &'static str,
+}
+
+impl fmt::Display for ParseClassifierError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid {} on line {}", self.field, self.line)
+    }
+}
+
+impl Error for ParseClassifierError {}
+
+impl<L: FromStr> FromStr for Classifier<L> {
+    type Err = ParseClassifierError;
+
+    /// Parses a classifier from category definitions, one per line
+    ///
+    /// Every definition has four fields separated by whitespace: the label, the hue range as
+    /// `<center>+-<margin>` in degrees or `-` for no hue, and the lightness and chroma ranges
+    /// as `<min>..<max>`. Either end of a range can be left out. Empty lines and lines starting
+    /// with `#` are ignored.
+    ///
+    /// ```text
+    /// # label  hue       lightness  chroma
+    /// grey     -         0.3..0.9   ..0.06
+    /// teal     210+-20   0.3..0.7   0.04..
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn range(field: &str, min: f32, max: f32) -> Option<RangeInclusive<f32>> {
+            let mut ends = field.splitn(2, "..");
+            let mut end = |default: f32| match ends.next()? {
+                "" => Some(default),
+                x => x.parse().ok(),
+            };
+            Some(end(min)?..=end(max)?)
+        }
+
+        fn hue(field: &str) -> Option<Option<HueRange>> {
+            if field == "-" {
+                return Some(None);
+            }
+            let mut parts = field.splitn(2, "+-");
+            let center = parts.next()?.parse().ok()?;
+            let margin = parts.next()?.parse().ok()?;
+            Some(Some(HueRange::new(center, margin)))
+        }
+
+        let mut classifier = Classifier::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let err = |field| ParseClassifierError { line: i + 1, field };
+            let mut fields = line.split_whitespace();
+            let mut next = |field| fields.next().ok_or_else(|| err(field));
+
+            let label = next("label")?.parse().map_err(|_| err("label"))?;
+            let hue = hue(next("hue")?).ok_or_else(|| err("hue"))?;
+            let lightness = range(next("lightness")?, 0.0, 1.0).ok_or_else(|| err("lightness"))?;
+            let chroma = range(next("chroma")?, 0.0, f32::INFINITY).ok_or_else(|| err("chroma"))?;
+
+            classifier.add(Category {
+                label,
+                hue,
+                lightness,
+                chroma,
+            });
+        }
+
+        Ok(classifier)
+    }
+}
@@ -0,0 +1,314 @@
+use std::fmt;
+
+use crate::*;
+
+/// A color in the subtractive CMY model
+///
+/// The channels are the amounts of cyan, magenta and yellow ink, so they're the complements of
+/// the red, green and blue channels. This is a naive model: the inks are assumed to be perfect,
+/// so the conversions don't match any real printer without a color profile.
+#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct CMYColor<T> {
+    pub c: T,
+    pub m: T,
+    pub y: T,
+}
+
+impl<T> CMYColor<T> {
+    /// Deconstructs this color into a tuple of it's channels
+    #[inline]
+    pub fn tuple(self) -> (T, T, T) {
+        (self.c, self.m, self.y)
+    }
+
+    /// Deconstructs this color into an array of it's channels
+    #[inline]
+    pub fn array(self) -> [T; 3] {
+        [self.c, self.m, self.y]
+    }
+}
+
+impl<T: Channel> CMYColor<T> {
+    /// Creates a new CMY color, clamping the channels to their range
+    pub fn new(c: T, m: T, y: T) -> Self {
+        CMYColor { c, m, y }.normalize()
+    }
+
+    /// Converts an RGB color into CMY
+    pub fn from_rgb<S>(color: RGBColor<T, S>) -> Self {
+        let (r, g, b) = color.tuple();
+        CMYColor {
+            c: invert(r),
+            m: invert(g),
+            y: invert(b),
+        }
+    }
+
+    /// Converts this color into RGB
+    pub fn rgb<S>(self) -> RGBColor<T, S> {
+        RGBColor::new(invert(self.c), invert(self.m), invert(self.y))
+    }
+
+    /// Converts the channels of this color into another type
+    #[inline]
+    pub fn conv<U: Channel>(self) -> CMYColor<U> {
+        CMYColor {
+            c: self.c.conv(),
+            m: self.m.conv(),
+            y: self.y.conv(),
+        }
+    }
+}
+
+/// Returns the complement of the channel value
+fn invert<T: Channel>(x: T) -> T {
+    (1.0 - x.conv::<f32>()).conv()
+}
+
+/// How much black ink is used when converting into CMYK
+///
+/// The grey component of a color, the smallest of it's cyan, magenta and yellow channels, can be
+/// printed with black ink instead of the three colored inks. This saves ink and gives deeper
+/// shadows.
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
+pub struct BlackGeneration {
+    /// The fraction of the grey component that is printed with black, between 0 and 1
+    pub black: f32,
+    /// The fraction of the black that is removed from the colored inks, between 0 and 1
+    ///
+    /// This is the under color removal. With full removal the CMYK color gives back the
+    /// original RGB color, with less the colored inks are printed under the black and the dark
+    /// colors get darker.
+    pub removal: f32,
+}
+
+impl BlackGeneration {
+    /// No black ink is used, the color is printed with only the colored inks
+    pub const NONE: BlackGeneration = BlackGeneration {
+        black: 0.0,
+        removal: 0.0,
+    };
+
+    /// All of the grey component is printed with black ink
+    ///
+    /// This is the usual naive conversion and the default.
+    pub const FULL: BlackGeneration = BlackGeneration {
+        black: 1.0,
+        removal: 1.0,
+    };
+
+    /// Creates new black generation parameters, clamping them between 0 and 1
+    pub fn new(black: f32, removal: f32) -> Self {
+        BlackGeneration {
+            black: black.conv(),
+            removal: removal.conv(),
+        }
+    }
+}
+
+impl Default for BlackGeneration {
+    fn default() -> Self {
+        BlackGeneration::FULL
+    }
+}
+
+/// A color in the subtractive CMYK model
+///
+/// Like `CMYColor`, but with a separate channel for the black ink, the key. The conversions are
+/// naive, see `BlackGeneration` for how the black channel is made.
+#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct CMYKColor<T> {
+    pub c: T,
+    pub m: T,
+    pub y: T,
+    pub k: T,
+}
+
+impl<T> CMYKColor<T> {
+    /// Deconstructs this color into a tuple of it's channels
+    #[inline]
+    pub fn tuple(self) -> (T, T, T, T) {
+        (self.c, self.m, self.y, self.k)
+    }
+
+    /// Deconstructs this color into an array of it's channels
+    #[inline]
+    pub fn array(self) -> [T; 4] {
+        [self.c, self.m, self.y, self.k]
+    }
+}
+
+impl<T: Channel> CMYKColor<T> {
+    /// Creates a new CMYK color
+    ///
+    /// The color is normalized on creation.
+    pub fn new(c: T, m: T, y: T, k: T) -> Self {
+        CMYKColor { c, m, y, k }.normalize()
+    }
+
+    /// Converts an RGB color into CMYK using the given amount of black
+    pub fn from_rgb<S>(color: RGBColor<T, S>, generation: BlackGeneration) -> Self {
+        CMYKColor::from_cmy(CMYColor::from_rgb(color), generation)
+    }
+
+    /// Converts a CMY color into CMYK using the given amount of black
+    pub fn from_cmy(color: CMYColor<T>, generation: BlackGeneration) -> Self {
+        let (c, m, y) = color.conv::<f32>().tuple();
+        let k = c.min(m).min(y) * generation.black;
+
+        // the colored inks needed to print the same color on top of the black
+        let under = |x: f32| {
+            let removed = if k < 1.0 { (x - k) / (1.0 - k) } else { 0.0 };
+            (x + (removed - x) * generation.removal).conv::<T>()
+        };
+        CMYKColor {
+            c: under(c),
+            m: under(m),
+            y: under(y),
+            k: k.conv(),
+        }
+    }
+
+    /// Converts this color into CMY by printing the black with the colored inks
+    pub fn cmy(self) -> CMYColor<T> {
+        let k = self.k.conv::<f32>();
+        let over = |x: T| (x.conv::<f32>() * (1.0 - k) + k).conv::<T>();
+        CMYColor {
+            c: over(self.c),
+            m: over(self.m),
+            y: over(self.y),
+        }
+    }
+
+    /// Converts this color into RGB
+    pub fn rgb<S>(self) -> RGBColor<T, S> {
+        self.cmy().rgb()
+    }
+
+    /// Converts the channels of this color into another type
+    #[inline]
+    pub fn conv<U: Channel>(self) -> CMYKColor<U> {
+        CMYKColor {
+            c: self.c.conv(),
+            m: self.m.conv(),
+            y: self.y.conv(),
+            k: self.k.conv(),
+        }
+    }
+}
+
+impl<T: Channel> Color for CMYColor<T> {
+    /// Clamps the channels to their range
+    fn normalize(self) -> Self {
+        CMYColor {
+            c: self.c.clamp(),
+            m: self.m.clamp(),
+            y: self.y.clamp(),
+        }
+    }
+
+    fn is_normal(&self) -> bool {
+        self.c.in_range() && self.m.in_range() && self.y.in_range()
+    }
+}
+
+impl<T: Channel> Color for CMYKColor<T> {
+    /// Clamps the channels to their range and zeroes the colored inks under full black
+    ///
+    /// A full black channel covers the other inks, so all the colors with it are the same.
+    fn normalize(self) -> Self {
+        let k = self.k.clamp();
+        if k == T::ch_max() {
+            CMYKColor {
+                c: T::ch_zero(),
+                m: T::ch_zero(),
+                y: T::ch_zero(),
+                k,
+            }
+        } else {
+            CMYKColor {
+                c: self.c.clamp(),
+                m: self.m.clamp(),
+                y: self.y.clamp(),
+                k,
+            }
+        }
+    }
+
+    fn is_normal(&self) -> bool {
+        let (c, m, y, k) = (&self.c, &self.m, &self.y, &self.k);
+        if !c.in_range() || !m.in_range() || !y.in_range() || !k.in_range() {
+            false
+        } else if *k == T::ch_max() {
+            let zero = T::ch_zero();
+            *c == zero && *m == zero && *y == zero
+        } else {
+            true
+        }
+    }
+}
+
+impl<T: Channel, S> From<RGBColor<T, S>> for CMYColor<T> {
+    #[inline]
+    fn from(color: RGBColor<T, S>) -> Self {
+        CMYColor::from_rgb(color)
+    }
+}
+
+impl<T: Channel, S> From<CMYColor<T>> for RGBColor<T, S> {
+    #[inline]
+    fn from(color: CMYColor<T>) -> Self {
+        color.rgb()
+    }
+}
+
+impl<T: Channel, S> From<RGBColor<T, S>> for CMYKColor<T> {
+    /// Converts the color using the full black generation
+    #[inline]
+    fn from(color: RGBColor<T, S>) -> Self {
+        CMYKColor::from_rgb(color, BlackGeneration::FULL)
+    }
+}
+
+impl<T: Channel, S> From<CMYKColor<T>> for RGBColor<T, S> {
+    #[inline]
+    fn from(color: CMYKColor<T>) -> Self {
+        color.rgb()
+    }
+}
+
+impl<T: Channel> From<CMYKColor<T>> for CMYColor<T> {
+    #[inline]
+    fn from(color: CMYKColor<T>) -> Self {
+        color.cmy()
+    }
+}
+
+impl<T: Channel + Clone> fmt::Display for CMYColor<T> {
+    /// Shows the channels as percentages
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let p = |x: &T| x.clone().conv::<f32>() * 100.0;
+        write!(
+            f,
+            "{:>5.1}%,{:>5.1}%,{:>5.1}%",
+            p(&self.c),
+            p(&self.m),
+            p(&self.y)
+        )
+    }
+}
+
+impl<T: Channel + Clone> fmt::Display for CMYKColor<T> {
+    /// Shows the channels as percentages
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let p = |x: &T| x.clone().conv::<f32>() * 100.0;
+        write!(
+            f,
+            "{:>5.1}%,{:>5.1}%,{:>5.1}%,{:>5.1}%",
+            p(&self.c),
+            p(&self.m),
+            p(&self.y),
+            p(&self.k)
+        )
+    }
+}
@@ -0,0 +1,263 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::space::std_gamma_encode;
+use crate::*;
+
+/// The WCAG 2.x conformance levels
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum WcagLevel {
+    AA,
+    AAA,
+}
+
+/// The text size categories used by WCAG 2.x
+///
+/// Large text is at least 18 points, or at least 14 points if bold.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum TextSize {
+    Normal,
+    Large,
+}
+
+impl WcagLevel {
+    /// Returns the minimum contrast ratio required by this level for the given text size
+    pub fn min_ratio(self, size: TextSize) -> f32 {
+        match (self, size) {
+            (WcagLevel::AA, TextSize::Normal) => 4.5,
+            (WcagLevel::AA, TextSize::Large) => 3.0,
+            (WcagLevel::AAA, TextSize::Normal) => 7.0,
+            (WcagLevel::AAA, TextSize::Large) => 4.5,
+        }
+    }
+}
+
+/// A contrast requirement for `find_contrasting_color`
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
+pub enum ContrastTarget {
+    /// The minimum WCAG 2.1 contrast ratio
+    Wcag(f32),
+    /// The minimum absolute APCA lightness contrast, regardless of polarity
+    Apca(f32),
+}
+
+impl ContrastTarget {
+    /// Returns the contrast of the text against the background measured by this target's method
+    ///
+    /// For APCA the absolute value of the lightness contrast is returned.
+    pub fn contrast<A: ToLinear, B: ToLinear>(&self, text: &A, background: &B) -> f32 {
+        match self {
+            ContrastTarget::Wcag(_) => contrast_ratio(text, background),
+            ContrastTarget::Apca(_) => apca_contrast(text, background).abs(),
+        }
+    }
+
+    /// Returns true if the text has enough contrast against the background
+    pub fn is_met<A: ToLinear, B: ToLinear>(&self, text: &A, background: &B) -> bool {
+        let min = match *self {
+            ContrastTarget::Wcag(min) | ContrastTarget::Apca(min) => min,
+        };
+        self.contrast(text, background) >= min
+    }
+}
+
+/// Error returned when no color can reach the requested contrast
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
+pub struct ContrastError {
+    /// The best contrast that can be reached by changing the lightness
+    pub best_contrast: f32,
+}
+
+impl fmt::Display for ContrastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "contrast target can't be reached, the best possible contrast is {:.2}",
+            self.best_contrast
+        )
+    }
+}
+
+impl Error for ContrastError {}
+
+/// Returns the WCAG 2.1 contrast ratio between the colors
+///
+/// The ratio is between 1 and 21, and it's the same regardless of the order of the colors.
+pub fn contrast_ratio<A: ToLinear, B: ToLinear>(a: &A, b: &B) -> f32 {
+    let la = a.to_linear().relative_luminance();
+    let lb = b.to_linear().relative_luminance();
+    let (lighter, darker) = if la > lb { (la, lb) } else { (lb, la) };
+
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Returns true if the text color has enough contrast against the background for the given
+/// WCAG 2.1 level and text size
+pub fn passes_wcag<A: ToLinear, B: ToLinear>(
+    text: &A,
+    background: &B,
+    level: WcagLevel,
+    size: TextSize,
+) -> bool {
+    contrast_ratio(text, background) >= level.min_ratio(size)
+}
+
+/// Returns the APCA lightness contrast (Lc) of the text color against the background
+///
+/// Implements the APCA-W3 0.0.98G-4g algorithm. Unlike the WCAG contrast ratio, the order of the
+/// colors matters. The result is roughly between -108 and 106, positive for dark text on a light
+/// background and negative for light text on a dark background. Values near zero mean there is
+/// no usable contrast.
+pub fn apca_contrast<A: ToLinear, B: ToLinear>(text: &A, background: &B) -> f32 {
+    const BLACK_THRESHOLD: f32 = 0.022;
+    const BLACK_CLAMP: f32 = 1.414;
+    const DELTA_Y_MIN: f32 = 0.0005;
+    const SCALE: f32 = 1.14;
+    const LOW_CLIP: f32 = 0.1;
+    const LOW_OFFSET: f32 = 0.027;
+
+    // APCA estimates the screen luminance with a simple power curve from the sRGB values
+    let luminance = |color: LinRGBColor| {
+        let (r, g, b) = color.map(|c| std_gamma_encode(c).powf(2.4)).tuple();
+        let y = 0.212_672_9 * r + 0.715_152_2 * g + 0.072_175 * b;
+        if y < BLACK_THRESHOLD {
+            y + (BLACK_THRESHOLD - y).powf(BLACK_CLAMP)
+        } else {
+            y
+        }
+    };
+
+    let y_text = luminance(text.to_linear());
+    let y_bg = luminance(background.to_linear());
+
+    if (y_bg - y_text).abs() < DELTA_Y_MIN {
+        return 0.0;
+    }
+
+    let lc = if y_bg > y_text {
+        // dark text on a light background
+        let sapc = (y_bg.powf(0.56) - y_text.powf(0.57)) * SCALE;
+        if sapc < LOW_CLIP {
+            0.0
+        } else {
+            sapc - LOW_OFFSET
+        }
+    } else {
+        // light text on a dark background
+        let sapc = (y_bg.powf(0.65) - y_text.powf(0.62)) * SCALE;
+        if sapc > -LOW_CLIP {
+            0.0
+        } else {
+            sapc + LOW_OFFSET
+        }
+    };
+
+    lc * 100.0
+}
+
+/// Returns the candidate color with the highest contrast ratio against the background
+///
+/// Returns `None` if there are no candidates.
+pub fn best_text_color<B, C, I>(background: &B, candidates: I) -> Option<C>
+where
+    B: ToLinear,
+    C: ToLinear,
+    I: IntoIterator<Item = C>,
+{
+    candidates
+        .into_iter()
+        .map(|c| (contrast_ratio(&c, background), c))
+        .fold(None, |best: Option<(f32, C)>, (ratio, c)| match best {
+            Some((best_ratio, _)) if best_ratio >= ratio => best,
+            _ => Some((ratio, c)),
+        })
+        .map(|(_, c)| c)
+}
+
+/// Finds the color nearest to the foreground that has enough contrast against the background
+///
+/// Only the lightness of the foreground is changed, its hue and chroma in the Oklch space are
+/// preserved. If the chroma doesn't fit inside the RGB gamut at the new lightness, it's reduced
+/// as little as possible.
+///
+/// Both lighter and darker colors are searched and the one with the smaller lightness change is
+/// returned. If the foreground already meets the target, it's returned as is.
+///
+/// Returns an error with the best contrast found if no color of the result type meets the
+/// target.
+pub fn find_contrasting_color<C, B>(
+    foreground: &C,
+    background: &B,
+    target: ContrastTarget,
+) -> Result<C, ContrastError>
+where
+    C: ToLinear + FromLinear,
+    B: ToLinear,
+{
+    let lch = foreground.to_linear().oklch::<Deg<f32>>();
+
+    // the foreground with the given lightness, with it's chroma fit into the gamut
+    let with_lightness = |l: f32| {
+        let color = OklchColor::new(l, lch.c, lch.h);
+        OklchColor::new(l, lch.c.min(color.max_chroma()), lch.h).to_linear()
+    };
+
+    if target.is_met(foreground, background) {
+        return Ok(C::from_linear(foreground.to_linear()));
+    }
+
+    // binary search for the lightness closest to the original in the given direction
+    let search = |limit: f32| -> Option<f32> {
+        if !target.is_met(&with_lightness(limit), background) {
+            return None;
+        }
+        let (mut failing, mut passing) = (lch.l, limit);
+        for _ in 0..24 {
+            let mid = (failing + passing) / 2.0;
+            if target.is_met(&with_lightness(mid), background) {
+                passing = mid;
+            } else {
+                failing = mid;
+            }
+        }
+        Some(passing)
+    };
+
+    let (mut lightness, limit) = match (search(1.0), search(0.0)) {
+        (Some(lighter), Some(darker)) => {
+            if lighter - lch.l <= lch.l - darker {
+                (lighter, 1.0)
+            } else {
+                (darker, 0.0)
+            }
+        }
+        (Some(l), None) => (l, 1.0),
+        (None, Some(l)) => (l, 0.0),
+        (None, None) => {
+            let best_contrast = target
+                .contrast(&with_lightness(1.0), background)
+                .max(target.contrast(&with_lightness(0.0), background));
+            return Err(ContrastError { best_contrast });
+        }
+    };
+
+    // rounding to the channels of the result type can put the color just under the target, so
+    // keep moving towards the limit until the result itself passes
+    const STEP: f32 = 0.001;
+    let mut best_contrast = 0.0f32;
+    loop {
+        let color = C::from_linear(with_lightness(lightness));
+        if target.is_met(&color, background) {
+            return Ok(color);
+        }
+        best_contrast = best_contrast.max(target.contrast(&color, background));
+        if lightness == limit {
+            return Err(ContrastError { best_contrast });
+        }
+        lightness = if (lightness - limit).abs() <= STEP {
+            limit
+        } else {
+            lightness + STEP.copysign(limit - lightness)
+        };
+    }
+}
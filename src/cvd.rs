@@ -0,0 +1,95 @@
+use crate::*;
+
+/// A type of color vision deficiency
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Deficiency {
+    /// Missing or anomalous long-wavelength (red) cones
+    Protan,
+    /// Missing or anomalous medium-wavelength (green) cones
+    Deutan,
+    /// Missing or anomalous short-wavelength (blue) cones
+    Tritan,
+    /// No color vision at all
+    Achromat,
+}
+
+type Matrix = [[f32; 3]; 3];
+
+// The dichromacy simulation matrices for linear RGB from Machado, Oliveira & Fernandes (2009),
+// "A Physiologically-based Model for Simulation of Color Vision Deficiency"
+const PROTANOPIA: Matrix = [
+    [0.152_286, 1.052_583, -0.204_868],
+    [0.114_503, 0.786_281, 0.099_216],
+    [-0.003_882, -0.048_116, 1.051_998],
+];
+
+const DEUTERANOPIA: Matrix = [
+    [0.367_322, 0.860_646, -0.227_968],
+    [0.280_085, 0.672_501, 0.047_413],
+    [-0.011_82, 0.042_94, 0.968_881],
+];
+
+const TRITANOPIA: Matrix = [
+    [1.255_528, -0.076_749, -0.178_779],
+    [-0.078_411, 0.930_809, 0.147_602],
+    [0.004_733, 0.691_367, 0.303_9],
+];
+
+// The rows tell how much of the red, green and blue error is moved to each channel in
+// daltonization, from Fidaner, Lin & Ozguven (2005)
+const RED_ERROR_SHIFT: Matrix = [[0.0, 0.0, 0.0], [0.7, 1.0, 0.0], [0.7, 0.0, 1.0]];
+const BLUE_ERROR_SHIFT: Matrix = [[1.0, 0.0, 0.7], [0.0, 1.0, 0.7], [0.0, 0.0, 0.0]];
+
+fn transform(m: &Matrix, (r, g, b): (f32, f32, f32)) -> (f32, f32, f32) {
+    let row = |i: usize| m[i][0] * r + m[i][1] * g + m[i][2] * b;
+    (row(0), row(1), row(2))
+}
+
+impl RGBColor<f32, LinearSpace> {
+    /// Simulates how this color is seen with the given color vision deficiency
+    ///
+    /// `severity` is between 0 and 1, where 1 simulates the complete lack of the cone type
+    /// (eg. protanopia) and smaller values simulate anomalous trichromacy (eg. protanomaly).
+    /// With `Achromat` it tells how much of the color is lost.
+    ///
+    /// Dichromacy is simulated with the matrices of Machado et al. (2009). Anomalous trichromacy
+    /// is approximated by interpolating between the normal vision and dichromacy.
+    pub fn simulate_cvd(self, deficiency: Deficiency, severity: f32) -> Self {
+        let severity = Channel::clamp(severity);
+        let rgb = self.tuple();
+
+        let (r, g, b) = match deficiency {
+            Deficiency::Protan => transform(&PROTANOPIA, rgb),
+            Deficiency::Deutan => transform(&DEUTERANOPIA, rgb),
+            Deficiency::Tritan => transform(&TRITANOPIA, rgb),
+            Deficiency::Achromat => {
+                let lum = self.relative_luminance();
+                (lum, lum, lum)
+            }
+        };
+
+        self.blend(&(r, g, b).into(), 1.0 - severity)
+    }
+
+    /// Corrects this color to be easier to distinguish with the given color vision deficiency
+    ///
+    /// The information lost in the simulated deficiency is shifted to the channels that are
+    /// still seen. Achromats can't be helped, so with `Achromat` the color is returned as is.
+    pub fn daltonize(self, deficiency: Deficiency, severity: f32) -> Self {
+        let shift = match deficiency {
+            Deficiency::Protan | Deficiency::Deutan => &RED_ERROR_SHIFT,
+            Deficiency::Tritan => &BLUE_ERROR_SHIFT,
+            Deficiency::Achromat => return self,
+        };
+
+        let simulated = self.simulate_cvd(deficiency, severity);
+        let error = (
+            self.r - simulated.r,
+            self.g - simulated.g,
+            self.b - simulated.b,
+        );
+        let (r, g, b) = transform(shift, error);
+
+        (self.r + r, self.g + g, self.b + b).into()
+    }
+}
@@ -0,0 +1,224 @@
+//! Dithering images to a fixed palette
+//!
+//! The images are buffers of linear RGB colors in row-major order. The dithering functions map
+//! every pixel to the index of a palette color, with the errors spread so that the average
+//! colors of the areas stay close to the original.
+
+use crate::*;
+
+/// A distance metric for finding the nearest palette color
+///
+/// The colors are first converted to points in the metric's space, so that the palette doesn't
+/// need to be converted again for every pixel.
+pub trait Metric {
+    /// A color in the space of this metric
+    type Point;
+
+    /// Converts the color into the space of this metric
+    fn point(&self, color: LinRGBColor) -> Self::Point;
+
+    /// Returns the distance between two points, or anything that grows with it
+    fn distance(&self, a: &Self::Point, b: &Self::Point) -> f32;
+}
+
+/// The euclidean distance in the linear RGB space
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct LinearDistance;
+
+impl Metric for LinearDistance {
+    type Point = LinRGBColor;
+
+    #[inline]
+    fn point(&self, color: LinRGBColor) -> LinRGBColor {
+        color
+    }
+
+    #[inline]
+    fn distance(&self, a: &LinRGBColor, b: &LinRGBColor) -> f32 {
+        (a.r - b.r).powi(2) + (a.g - b.g).powi(2) + (a.b - b.b).powi(2)
+    }
+}
+
+/// The euclidean distance in the Oklab space, which approximates the perceived difference
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct OklabDistance;
+
+impl Metric for OklabDistance {
+    type Point = OklabColor<f32>;
+
+    #[inline]
+    fn point(&self, color: LinRGBColor) -> OklabColor<f32> {
+        // errors can push the pixels out of the gamut, don't let that affect the lightness
+        color.map(|x| x.max(0.0)).oklab()
+    }
+
+    #[inline]
+    fn distance(&self, a: &OklabColor<f32>, b: &OklabColor<f32>) -> f32 {
+        (a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)
+    }
+}
+
+/// A dithering method
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Dither {
+    /// No dithering, every pixel is mapped to the nearest palette color
+    None,
+    /// Ordered dithering with a Bayer matrix of size `2^order`
+    ///
+    /// Orders from 2 to 4 are the most useful, and orders above `MAX_BAYER_ORDER` are clamped to
+    /// it.
+    ///
+    /// `spread` is how much the threshold matrix can move the colors in the linear space. A good
+    /// value is about the distance between the palette colors.
+    Bayer { order: u32, spread: f32 },
+    /// Floyd-Steinberg error diffusion
+    FloydSteinberg,
+    /// Atkinson error diffusion, which only spreads 3/4 of the error for higher contrast
+    Atkinson,
+    /// Jarvis, Judice & Ninke error diffusion, which spreads the error further than
+    /// Floyd-Steinberg
+    JarvisJudiceNinke,
+}
+
+/// The largest supported order of the Bayer matrix, which makes a 256×256 matrix
+pub const MAX_BAYER_ORDER: u32 = 8;
+
+/// An error diffusion kernel as `(dx, dy, weight)` entries and the divisor of the weights
+type Kernel = (&'static [(isize, usize, f32)], f32);
+
+const FLOYD_STEINBERG: Kernel = (&[(1, 0, 7.0), (-1, 1, 3.0), (0, 1, 5.0), (1, 1, 1.0)], 16.0);
+
+const ATKINSON: Kernel = (
+    &[
+        (1, 0, 1.0),
+        (2, 0, 1.0),
+        (-1, 1, 1.0),
+        (0, 1, 1.0),
+        (1, 1, 1.0),
+        (0, 2, 1.0),
+    ],
+    8.0,
+);
+
+#[rustfmt::skip]
+const JARVIS_JUDICE_NINKE: Kernel = (
+    &[
+                                      (1, 0, 7.0), (2, 0, 5.0),
+        (-2, 1, 3.0), (-1, 1, 5.0), (0, 1, 7.0), (1, 1, 5.0), (2, 1, 3.0),
+        (-2, 2, 1.0), (-1, 2, 3.0), (0, 2, 5.0), (1, 2, 3.0), (2, 2, 1.0),
+    ],
+    48.0,
+);
+
+/// Returns the Bayer threshold matrix of size `2^order` with values in `0..4^order`
+fn bayer_matrix(order: u32) -> Vec<Vec<usize>> {
+    let mut matrix = vec![vec![0]];
+    for _ in 0..order {
+        let n = matrix.len();
+        let mut next = vec![vec![0; n * 2]; n * 2];
+        for (y, row) in matrix.iter().enumerate() {
+            for (x, &m) in row.iter().enumerate() {
+                next[y][x] = 4 * m;
+                next[y][x + n] = 4 * m + 2;
+                next[y + n][x] = 4 * m + 3;
+                next[y + n][x + n] = 4 * m + 1;
+            }
+        }
+        matrix = next;
+    }
+    matrix
+}
+
+/// Dithers the image to the palette
+///
+/// `pixels` has the rows of the image one after another, and `width` is the length of a row.
+/// Returns the palette index of every pixel, or an empty vector if the palette is empty.
+///
+/// The errors are computed and diffused in the linear RGB space, and the nearest palette colors
+/// are searched with the given metric.
+///
+/// # Panics
+/// If the length of `pixels` isn't a multiple of `width`.
+pub fn dither<M: Metric>(
+    pixels: &[LinRGBColor],
+    width: usize,
+    palette: &[SRGB24Color],
+    method: Dither,
+    metric: &M,
+) -> Vec<usize> {
+    assert!(
+        pixels.is_empty() || (width != 0 && pixels.len() % width == 0),
+        "the image isn't {} pixels wide",
+        width
+    );
+    if pixels.is_empty() || palette.is_empty() {
+        return Vec::new();
+    }
+
+    let linear: Vec<LinRGBColor> = palette.iter().map(ToLinear::to_linear).collect();
+    let points: Vec<M::Point> = linear.iter().map(|&c| metric.point(c)).collect();
+    let nearest = |color: LinRGBColor| {
+        let point = metric.point(color);
+        (0..points.len())
+            .map(|i| (i, metric.distance(&point, &points[i])))
+            .fold(
+                (0, f32::INFINITY),
+                |best, (i, d)| if d < best.1 { (i, d) } else { best },
+            )
+            .0
+    };
+
+    let kernel = match method {
+        Dither::None => return pixels.iter().map(|&c| nearest(c)).collect(),
+        Dither::Bayer { order, spread } => {
+            let matrix = bayer_matrix(order.min(MAX_BAYER_ORDER));
+            let (size, levels) = (matrix.len(), (matrix.len() * matrix.len()) as f32);
+            return pixels
+                .iter()
+                .enumerate()
+                .map(|(i, &color)| {
+                    let (x, y) = (i % width, i / width);
+                    let threshold = (matrix[y % size][x % size] as f32 + 0.5) / levels - 0.5;
+                    nearest(color.map(|c| c + threshold * spread))
+                })
+                .collect();
+        }
+        Dither::FloydSteinberg => FLOYD_STEINBERG,
+        Dither::Atkinson => ATKINSON,
+        Dither::JarvisJudiceNinke => JARVIS_JUDICE_NINKE,
+    };
+
+    let (weights, divisor) = kernel;
+    let height = pixels.len() / width;
+    let mut buffer = pixels.to_vec();
+    let mut indices = Vec::with_capacity(pixels.len());
+
+    for y in 0..height {
+        for x in 0..width {
+            let color = buffer[y * width + x];
+            let index = nearest(color);
+            indices.push(index);
+
+            // the buffer isn't clamped so that the errors can accumulate past the gamut
+            let palette_color = linear[index];
+            let error = (
+                color.r - palette_color.r,
+                color.g - palette_color.g,
+                color.b - palette_color.b,
+            );
+            for &(dx, dy, weight) in weights {
+                let (nx, ny) = (x as isize + dx, y + dy);
+                if nx < 0 || nx as usize >= width || ny >= height {
+                    continue;
+                }
+                let target = &mut buffer[ny * width + nx as usize];
+                let weight = weight / divisor;
+                target.r += error.0 * weight;
+                target.g += error.1 * weight;
+                target.b += error.2 * weight;
+            }
+        }
+    }
+
+    indices
+}
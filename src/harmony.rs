@@ -0,0 +1,94 @@
+//! Color harmony generators
+//!
+//! All the functions take the base color as the first color of the returned set, and generate
+//! the others by rotating it's hue with `ColorOps::shift_hue`.
+//!
+//! The hue rotation happens in the space of the given color, so for RGB and HSV colors it's
+//! done in HSV. To compute the harmonies in a perceptual space, give an `OklchColor` instead.
+
+use crate::*;
+
+/// Returns the color and it's complement on the opposite side of the color wheel
+pub fn complementary<C: ColorOps + Clone>(color: C) -> [C; 2] {
+    [color.clone(), color.shift_hue(Deg(180.0))]
+}
+
+/// Returns the color and the two colors on both sides of it's complement
+///
+/// `angle` is the distance of the two colors from the complement.
+pub fn split_complementary<C, A>(color: C, angle: A) -> [C; 3]
+where
+    C: ColorOps + Clone,
+    A: Angle + Channel,
+{
+    let angle = angle.conv::<Deg<f32>>();
+    [
+        color.clone(),
+        color.clone().shift_hue(Deg(180.0) - angle),
+        color.shift_hue(Deg(180.0) + angle),
+    ]
+}
+
+/// Returns the color and it's two neighbours on the color wheel
+///
+/// `angle` is the distance of the neighbours from the color, usually 30°.
+pub fn analogous<C, A>(color: C, angle: A) -> [C; 3]
+where
+    C: ColorOps + Clone,
+    A: Angle + Channel,
+{
+    let angle = angle.conv::<Deg<f32>>();
+    [
+        color.clone(),
+        color.clone().shift_hue(Deg(0.0) - angle),
+        color.shift_hue(angle),
+    ]
+}
+
+/// Returns the color and two other colors evenly spaced around the color wheel
+pub fn triadic<C: ColorOps + Clone>(color: C) -> [C; 3] {
+    [
+        color.clone(),
+        color.clone().shift_hue(Deg(120.0)),
+        color.shift_hue(Deg(240.0)),
+    ]
+}
+
+/// Returns two complementary pairs forming a rectangle on the color wheel
+///
+/// `angle` is the distance between the color and the second pair.
+pub fn tetradic<C, A>(color: C, angle: A) -> [C; 4]
+where
+    C: ColorOps + Clone,
+    A: Angle + Channel,
+{
+    let angle = angle.conv::<Deg<f32>>();
+    [
+        color.clone(),
+        color.clone().shift_hue(angle),
+        color.clone().shift_hue(Deg(180.0)),
+        color.shift_hue(Deg(180.0) + angle),
+    ]
+}
+
+/// Returns the color and three other colors evenly spaced around the color wheel
+#[inline]
+pub fn square<C: ColorOps + Clone>(color: C) -> [C; 4] {
+    tetradic(color, Deg(90.0))
+}
+
+/// Returns the shades and tints of the color
+///
+/// The returned vector has `count` shades, the color itself and `count` tints, ordered from
+/// darkest to lightest. The shades and tints are evenly spaced between the color and black or
+/// white respectively, not including them.
+pub fn monochromatic<C: ColorOps + Clone>(color: C, count: usize) -> Vec<C> {
+    let step = 1.0 / (count + 1) as f32;
+
+    let shades = (1..=count)
+        .rev()
+        .map(|i| color.clone().darken(i as f32 * step));
+    let tints = (1..=count).map(|i| color.clone().lighten(i as f32 * step));
+
+    shades.chain(Some(color.clone())).chain(tints).collect()
+}
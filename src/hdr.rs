@@ -0,0 +1,341 @@
+use num_traits::Float;
+
+use crate::space::*;
+use crate::*;
+
+/// A linear RGB color with a high dynamic range
+///
+/// Unlike with `RGBColor`, the channels don't have an upper limit, so this can hold the colors of
+/// bright lights and emissive surfaces. The channels can't be negative.
+///
+/// The colors are in the linear space of sRGB with 1 being the white of a display. To show them,
+/// they need to be tone mapped into the displayable range with `tone_map`.
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq)]
+#[repr(C)]
+pub struct HDRColor<T> {
+    pub r: T,
+    pub g: T,
+    pub b: T,
+}
+
+/// A tone mapping operator
+///
+/// The operators compress the high dynamic range into the range from 0 to 1, keeping the dark
+/// and middle tones mostly as they are and rolling off the highlights.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ToneMap {
+    /// Clamps the channels to 1
+    Clamp,
+    /// The simple Reinhard operator `x / (1 + x)`, applied to every channel
+    Reinhard,
+    /// The extended Reinhard operator, which maps the given white level to 1
+    ///
+    /// The white level should be positive. Smaller levels and NaN are treated as the small
+    /// positive level of `f32::EPSILON`, so that the result stays finite.
+    ReinhardExtended { white: f32 },
+    /// Krzysztof Narkowicz's fit of the ACES filmic curve
+    AcesFilmic,
+    /// John Hable's filmic curve from Uncharted 2, with the white level of 11.2
+    Hable,
+    /// The AgX view transform, which desaturates the bright colors smoothly towards white
+    AgX,
+}
+
+impl<T> HDRColor<T> {
+    /// Deconstructs this color into a tuple of it's channels
+    #[inline]
+    pub fn tuple(self) -> (T, T, T) {
+        (self.r, self.g, self.b)
+    }
+
+    /// Applies the given function to all color channels
+    #[inline]
+    pub fn map<U, F: Fn(T) -> U>(self, fun: F) -> HDRColor<U> {
+        HDRColor {
+            r: fun(self.r),
+            g: fun(self.g),
+            b: fun(self.b),
+        }
+    }
+}
+
+impl<T: Float> HDRColor<T> {
+    /// Creates a new HDR color
+    ///
+    /// Negative values are clamped to zero.
+    pub fn new(r: T, g: T, b: T) -> Self {
+        HDRColor { r, g, b }.normalize()
+    }
+
+    /// Adjusts the exposure of this color by the given number of stops
+    ///
+    /// Every stop doubles the brightness, and negative stops halve it.
+    pub fn exposure(self, stops: T) -> Self {
+        let scale = cuwf::<T>(2.0).powf(stops);
+        self.map(|x| x * scale)
+    }
+
+    /// Returns the relative luminance of this color, which can be larger than 1
+    pub fn luminance(&self) -> T {
+        cuwf::<T>(0.2126) * self.r + cuwf::<T>(0.7152) * self.g + cuwf::<T>(0.0722) * self.b
+    }
+
+    /// Maps this color into the displayable linear range with the given operator
+    pub fn tone_map(self, operator: ToneMap) -> RGBColor<T, LinearSpace>
+    where
+        T: Channel,
+    {
+        let (r, g, b) = match operator {
+            ToneMap::Clamp => self.tuple(),
+            ToneMap::Reinhard => self.map(|x| x / (T::one() + x)).tuple(),
+            ToneMap::ReinhardExtended { white } => {
+                let white = if white >= f32::EPSILON {
+                    white
+                } else {
+                    f32::EPSILON
+                };
+                let white2 = cuwf::<T>(white * white);
+                self.map(|x| x * (T::one() + x / white2) / (T::one() + x))
+                    .tuple()
+            }
+            ToneMap::AcesFilmic => self
+                .map(|x| {
+                    let k = cuwf::<T>;
+                    x * (k(2.51) * x + k(0.03)) / (x * (k(2.43) * x + k(0.59)) + k(0.14))
+                })
+                .tuple(),
+            ToneMap::Hable => {
+                const WHITE: f32 = 11.2;
+                let white = hable(cuwf::<T>(WHITE));
+                self.map(|x| hable(x * cuwf(2.0)) / white).tuple()
+            }
+            ToneMap::AgX => agx(self.tuple()),
+        };
+        RGBColor::new(r, g, b)
+    }
+
+    /// Tone maps this color with the given operator and encodes it into sRGB
+    pub fn to_srgb(self, operator: ToneMap) -> RGBColor<T, SRGBSpace>
+    where
+        T: Channel,
+    {
+        self.tone_map(operator).std_encode()
+    }
+}
+
+/// The filmic curve of John Hable
+fn hable<T: Float>(x: T) -> T {
+    let k = cuwf::<T>;
+    let (a, b, c, d, e, f) = (k(0.15), k(0.50), k(0.10), k(0.20), k(0.02), k(0.30));
+    (x * (a * x + c * b) + d * e) / (x * (a * x + b) + d * f) - e / f
+}
+
+/// The AgX view transform with a polynomial fit of it's default contrast curve
+fn agx<T: Float>(color: (T, T, T)) -> (T, T, T) {
+    const INSET: [[f32; 3]; 3] = [
+        [0.842_479_06, 0.078_433_6, 0.079_223_745],
+        [0.042_328_24, 0.878_468_6, 0.079_166_13],
+        [0.042_375_654, 0.078_433_6, 0.879_143],
+    ];
+    const OUTSET: [[f32; 3]; 3] = [
+        [1.196_879, -0.098_020_88, -0.099_029_74],
+        [-0.052_896_85, 1.151_903_1, -0.098_961_18],
+        [-0.052_971_635, -0.098_043_45, 1.151_073_7],
+    ];
+    const MIN_EV: f32 = -12.473_93;
+    const MAX_EV: f32 = 4.026_069;
+
+    let transform = |m: &[[f32; 3]; 3], (r, g, b): (T, T, T)| {
+        let row =
+            |i: usize| cuwf::<T>(m[i][0]) * r + cuwf::<T>(m[i][1]) * g + cuwf::<T>(m[i][2]) * b;
+        (row(0), row(1), row(2))
+    };
+    let curve = |x: T| {
+        // encode logarithmically between the exposure limits
+        let x = x.max(T::min_positive_value()).log2();
+        let x = (x.max(cuwf(MIN_EV)).min(cuwf(MAX_EV)) - cuwf(MIN_EV)) / cuwf(MAX_EV - MIN_EV);
+
+        let (k, x2, x4) = (cuwf::<T>, x * x, x * x * x * x);
+        let y = k(15.5) * x4 * x2 - k(40.14) * x4 * x + k(31.96) * x4 - k(6.868) * x2 * x
+            + k(0.4298) * x2
+            + k(0.1191) * x
+            - k(0.002_32);
+        y.max(T::zero())
+    };
+
+    let (r, g, b) = transform(&INSET, color);
+    let (r, g, b) = transform(&OUTSET, (curve(r), curve(g), curve(b)));
+    // the curve gives display encoded values, which are linearized with the gamma of 2.2
+    let linear = |x: T| x.max(T::zero()).powf(cuwf(2.2));
+    (linear(r), linear(g), linear(b))
+}
+
+impl<T: Float> Color for HDRColor<T> {
+    /// Clamps the negative channels to zero
+    fn normalize(self) -> Self {
+        self.map(|x| x.max(T::zero()))
+    }
+
+    fn is_normal(&self) -> bool {
+        self.r >= T::zero() && self.g >= T::zero() && self.b >= T::zero()
+    }
+}
+
+impl<T: Float + Channel> ToLinear for HDRColor<T> {
+    /// Clamps the channels into the linear RGB range
+    ///
+    /// Use `tone_map` to compress the range instead.
+    #[inline]
+    fn to_linear(&self) -> LinRGBColor {
+        self.tone_map(ToneMap::Clamp).conv()
+    }
+}
+
+impl<T: Float + Channel> FromLinear for HDRColor<T> {
+    #[inline]
+    fn from_linear(color: LinRGBColor) -> Self {
+        color.conv::<T>().into()
+    }
+}
+
+impl<T: Float> From<RGBColor<T, LinearSpace>> for HDRColor<T> {
+    fn from(color: RGBColor<T, LinearSpace>) -> Self {
+        let (r, g, b) = color.tuple();
+        HDRColor::new(r, g, b)
+    }
+}
+
+impl<T: Float> From<(T, T, T)> for HDRColor<T> {
+    fn from(tuple: (T, T, T)) -> Self {
+        let (r, g, b) = tuple;
+        HDRColor::new(r, g, b)
+    }
+}
+
+pub(crate) type Matrix = [[f64; 3]; 3];
+
+/// Converts linear colors from the primaries of sRGB into the primaries of Rec. 2020
+pub(crate) const REC709_TO_REC2020: Matrix = [
+    [
+        0.627_403_895_934_699,
+        0.329_283_038_377_884,
+        0.043_313_065_687_417,
+    ],
+    [
+        0.069_097_289_358_232,
+        0.919_540_395_075_459,
+        0.011_362_315_566_309,
+    ],
+    [
+        0.016_391_438_875_15,
+        0.088_013_307_877_226,
+        0.895_595_253_247_624,
+    ],
+];
+
+/// Converts linear colors from the primaries of Rec. 2020 into the primaries of sRGB
+pub(crate) const REC2020_TO_REC709: Matrix = [
+    [
+        1.660_491_002_108_435,
+        -0.587_641_138_788_55,
+        -0.072_849_863_319_885,
+    ],
+    [
+        -0.124_550_474_521_591,
+        1.132_899_897_125_96,
+        -0.008_349_422_604_369,
+    ],
+    [
+        -0.018_150_763_354_905,
+        -0.100_578_898_008_007,
+        1.118_729_661_362_913,
+    ],
+];
+
+pub(crate) fn transform<T: Float>(m: &Matrix, (r, g, b): (T, T, T)) -> (T, T, T) {
+    let row = |i: usize| cuwd::<T>(m[i][0]) * r + cuwd::<T>(m[i][1]) * g + cuwd::<T>(m[i][2]) * b;
+    (row(0), row(1), row(2))
+}
+
+/// Returns the scene light of the HLG reference white, which is encoded as 0.75
+fn hlg_reference_white<T: Float>() -> T {
+    hlg_decode(cuwf(0.75))
+}
+
+impl<T: Float + Channel> RGBColor<T, Rec2100PQSpace> {
+    /// Encodes an HDR color with the Perceptual Quantizer
+    ///
+    /// `white_nits` is the luminance of the color 1, usually `REFERENCE_WHITE_NITS`. Colors
+    /// brighter than 10000 nits are clamped.
+    pub fn from_hdr(color: HDRColor<T>, white_nits: T) -> Self {
+        let (r, g, b) = transform(&REC709_TO_REC2020, color.tuple());
+        let encode = |x: T| nits_to_pq(x * white_nits);
+        RGBColor::new(encode(r), encode(g), encode(b))
+    }
+
+    /// Decodes this color into an HDR color
+    ///
+    /// `white_nits` is the luminance that is decoded as 1. The colors outside of the gamut of
+    /// sRGB are clamped into it.
+    pub fn to_hdr(self, white_nits: T) -> HDRColor<T> {
+        let decode = |x: T| pq_to_nits(x) / white_nits;
+        let (r, g, b) = transform(&REC2020_TO_REC709, self.map(decode).tuple());
+        HDRColor::new(r, g, b)
+    }
+}
+
+impl<T: Float + Channel> RGBColor<T, Rec2100HLGSpace> {
+    /// Encodes an HDR color with the Hybrid Log-Gamma curve
+    ///
+    /// The color 1 is encoded as the reference white with the signal of 0.75. The full signal
+    /// is reached at `1 / hlg_decode(0.75)`, about 3.8 times the reference white, and brighter
+    /// colors are clamped.
+    pub fn from_hdr(color: HDRColor<T>) -> Self {
+        let (r, g, b) = transform(&REC709_TO_REC2020, color.tuple());
+        let white = hlg_reference_white::<T>();
+        let encode = |x: T| hlg_encode(x * white);
+        RGBColor::new(encode(r), encode(g), encode(b))
+    }
+
+    /// Decodes this color into an HDR color
+    ///
+    /// The reference white is decoded as 1, and the colors outside of the gamut of sRGB are
+    /// clamped into it.
+    pub fn to_hdr(self) -> HDRColor<T> {
+        let white = hlg_reference_white::<T>();
+        let decode = |x: T| hlg_decode(x) / white;
+        let (r, g, b) = transform(&REC2020_TO_REC709, self.map(decode).tuple());
+        HDRColor::new(r, g, b)
+    }
+}
+
+impl<T: Float + Channel> ToLinear for RGBColor<T, Rec2100PQSpace> {
+    /// Decodes the color with the reference white of `REFERENCE_WHITE_NITS` and clamps it
+    #[inline]
+    fn to_linear(&self) -> LinRGBColor {
+        self.to_hdr(cuwf(REFERENCE_WHITE_NITS)).to_linear()
+    }
+}
+
+impl<T: Float + Channel> FromLinear for RGBColor<T, Rec2100PQSpace> {
+    /// Encodes the color with the reference white of `REFERENCE_WHITE_NITS`
+    #[inline]
+    fn from_linear(color: LinRGBColor) -> Self {
+        Self::from_hdr(HDRColor::from_linear(color), cuwf(REFERENCE_WHITE_NITS))
+    }
+}
+
+impl<T: Float + Channel> ToLinear for RGBColor<T, Rec2100HLGSpace> {
+    /// Decodes the color and clamps it
+    #[inline]
+    fn to_linear(&self) -> LinRGBColor {
+        self.to_hdr().to_linear()
+    }
+}
+
+impl<T: Float + Channel> FromLinear for RGBColor<T, Rec2100HLGSpace> {
+    #[inline]
+    fn from_linear(color: LinRGBColor) -> Self {
+        Self::from_hdr(HDRColor::from_linear(color))
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt;
 use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 use crate::*;
 
@@ -18,6 +19,19 @@ pub struct HSVColor<H, T, S> {
 }
 
 impl<H, T, S> HSVColor<H, T, S> {
+    /// Creates a new HSV value without normalizing it
+    ///
+    /// The channels are kept as they are, even if they are out of their ranges.
+    #[inline]
+    pub fn new_unchecked(h: H, s: T, v: T) -> Self {
+        HSVColor {
+            h,
+            s,
+            v,
+            _space: PhantomData,
+        }
+    }
+
     /// Deconstructs this color into a tuple of it's channels
     #[inline]
     pub fn tuple(self) -> (H, T, T) {
@@ -49,12 +63,27 @@ where
 }
 
 impl<H: Channel, T: Channel, S> HSVColor<H, T, S> {
+    /// Creates a new HSV value, or returns an error if any of the values is out of range
+    ///
+    /// The hue must be between zero and a full angle. The value is normalized like in `new`,
+    /// which only zeroes the channels that don't affect the color.
+    pub fn try_new(h: H, s: T, v: T) -> Result<Self, ChannelRangeError> {
+        Ok(HSVColor::new_unchecked(
+            check_range(h, "hue")?,
+            check_range(s, "saturation")?,
+            check_range(v, "value")?,
+        )
+        .normalize())
+    }
+
     /// Transform this color into RGB form
     ///
-    /// This should be done to a normalized HSV color.
+    /// The hue can be any angle, it's wrapped into it's normal range. A NaN hue is treated as
+    /// zero.
     pub fn rgb(self) -> RGBColor<T, S> {
-        let h = cuwtf(self.h.conv::<Deg<f32>>()) / 60.0;
-        let (s, v) = (cuwtf(self.s), cuwtf(self.v));
+        let h = (cuwtd(self.h.conv::<Deg<f64>>()) / 60.0).rem_euclid(6.0);
+        let h = if h.is_nan() { 0.0 } else { h };
+        let (s, v) = (self.s.conv::<f64>(), self.v.conv::<f64>());
 
         // largest, second largest and the smallest component
         let mc = s * v;
@@ -67,11 +96,10 @@ impl<H: Channel, T: Channel, S> HSVColor<H, T, S> {
             2 => (0., mc, xc),
             3 => (0., xc, mc),
             4 => (xc, 0., mc),
-            5 | 6 => (mc, 0., xc),
-            _ => panic!("Invalid hue value: {:?}", h),
+            _ => (mc, 0., xc),
         };
 
-        (cuwf::<T>(r + min), cuwf::<T>(g + min), cuwf::<T>(b + min)).into()
+        ((r + min).conv::<T>(), (g + min).conv(), (b + min).conv()).into()
     }
 
     /// Converts the channels of this color into another type
@@ -122,24 +150,30 @@ impl<H: Channel, T: Channel, S> Color for HSVColor<H, T, S> {
             false
         } else if *v == t0 {
             // color black
-            if *h == h0 && *s == t0 {
-                true
-            } else {
-                false
-            }
+            *h == h0 && *s == t0
         } else if *s == t0 {
             // a grey color
-            if *h == h0 {
-                true
-            } else {
-                false
-            }
+            *h == h0
         } else {
             true
         }
     }
 }
 
+impl<H: Channel + Clone, T: Channel + Clone, S: RGBSpace> ToLinear for HSVColor<H, T, S> {
+    #[inline]
+    fn to_linear(&self) -> LinRGBColor {
+        self.clone().conv::<H, f32>().rgb().decode()
+    }
+}
+
+impl<H: Channel, T: Channel, S: RGBSpace> FromLinear for HSVColor<H, T, S> {
+    #[inline]
+    fn from_linear(color: LinRGBColor) -> Self {
+        color.encode::<S>().hsv::<H>().conv()
+    }
+}
+
 impl<H: Channel, T: Channel> From<BaseColor> for HSVColor<H, T, SRGBSpace>
 where
     Self: Color,
@@ -148,18 +182,16 @@ where
     fn from(base_color: BaseColor) -> Self {
         use self::BaseColor::*;
 
-        let f = |h: f32, s: f32, v: f32| Self::new(Deg(h).conv::<H>(), s.conv(), v.conv());
+        let h = || base_color.hue().unwrap_or_default().conv::<H>();
+        let f = |s: f32, v: f32| Self::new(h(), s.conv(), v.conv());
 
         match base_color {
-            Black => f(0.0, 0.0, 0.0),
-            Grey => f(0.0, 0.0, 0.5),
-            White => f(0.0, 0.0, 1.0),
-            Red => f(0.0, 1.0, 1.0),
-            Yellow => f(60.0, 1.0, 1.0),
-            Green => f(120.0, 1.0, 1.0),
-            Cyan => f(180.0, 1.0, 1.0),
-            Blue => f(240.0, 1.0, 1.0),
-            Magenta => f(300.0, 1.0, 1.0),
+            Black => f(0.0, 0.0),
+            Grey => f(0.0, 0.5),
+            White => f(0.0, 1.0),
+            Pink => f(0.25, 1.0),
+            Brown => f(1.0, 0.5),
+            _ => f(1.0, 1.0),
         }
     }
 }
@@ -248,6 +280,60 @@ impl<H: Clone, T: Clone, S> Clone for HSVColor<H, T, S> {
 
 impl<H: Copy, T: Copy, S> Copy for HSVColor<H, T, S> {}
 
+impl<H, T, S> Add for HSVColor<H, T, S>
+where
+    Self: Color,
+    H: Add<Output = H>,
+    T: Add<Output = T>,
+{
+    type Output = Self;
+
+    /// Adds the channels of the colors together
+    ///
+    /// The hue wraps around and the result is normalized.
+    fn add(self, rhs: Self) -> Self::Output {
+        HSVColor::new(self.h + rhs.h, self.s + rhs.s, self.v + rhs.v)
+    }
+}
+
+impl<H, T, S> Sub for HSVColor<H, T, S>
+where
+    Self: Color,
+    H: Sub<Output = H>,
+    T: Sub<Output = T>,
+{
+    type Output = Self;
+
+    /// Subtracts the channels of the colors
+    ///
+    /// The hue wraps around and the result is normalized.
+    fn sub(self, rhs: Self) -> Self::Output {
+        HSVColor::new(self.h - rhs.h, self.s - rhs.s, self.v - rhs.v)
+    }
+}
+
+impl<H, T, S> AddAssign for HSVColor<H, T, S>
+where
+    Self: Color + Copy,
+    H: Add<Output = H>,
+    T: Add<Output = T>,
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<H, T, S> SubAssign for HSVColor<H, T, S>
+where
+    Self: Color + Copy,
+    H: Sub<Output = H>,
+    T: Sub<Output = T>,
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
 // TODO make more generic
 impl<S> fmt::Display for HSVColor<f32, f32, S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
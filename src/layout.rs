@@ -0,0 +1,131 @@
+use std::marker::PhantomData;
+
+use crate::*;
+
+/// Declares an RGB color with it's channels in another order and it's conversions
+macro_rules! layout {
+    (
+        $( #[$meta:meta] )*
+        $name:ident { $( $field:ident ),* }
+    ) => {
+        $( #[$meta] )*
+        #[derive(Debug, Eq, PartialEq)]
+        #[repr(C)]
+        pub struct $name<T, S> {
+            $( pub $field: T, )*
+            _space: PhantomData<S>,
+        }
+
+        impl<T, S> $name<T, S> {
+            /// Creates a new color with the channels in the order of it's memory layout
+            ///
+            /// The channels are not clamped.
+            #[inline]
+            pub fn new($( $field: T ),*) -> Self {
+                $name {
+                    $( $field, )*
+                    _space: PhantomData,
+                }
+            }
+
+            /// Deconstructs this color into an array of it's channels in the order of it's
+            /// memory layout
+            #[inline]
+            pub fn array(self) -> [T; layout!(@count $( $field )*)] {
+                [$( self.$field ),*]
+            }
+        }
+
+        impl<T: Clone, S> Clone for $name<T, S> {
+            fn clone(&self) -> Self {
+                $name {
+                    $( $field: self.$field.clone(), )*
+                    _space: PhantomData,
+                }
+            }
+        }
+
+        impl<T: Copy, S> Copy for $name<T, S> {}
+
+        impl<T, S> IntoIterator for $name<T, S> {
+            type Item = T;
+            type IntoIter = std::array::IntoIter<T, { layout!(@count $( $field )*) }>;
+
+            /// Iterates the channels in the order of the memory layout
+            fn into_iter(self) -> Self::IntoIter {
+                IntoIterator::into_iter(self.array())
+            }
+        }
+    };
+    ( @count $a:ident $b:ident $c:ident ) => { 3 };
+    ( @count $a:ident $b:ident $c:ident $d:ident ) => { 4 };
+}
+
+layout! {
+    /// An RGB color with the channels in the order blue, green and red
+    ///
+    /// The color has the same memory layout as `[T; 3]`, so BGR buffers can be cast into slices
+    /// of these with `Bgr::from_channels` and converted into `RGBColor`s.
+    Bgr { b, g, r }
+}
+
+layout! {
+    /// An RGBA color with the channels in the order blue, green, red and alpha
+    ///
+    /// The color has the same memory layout as `[T; 4]`, so BGRA buffers can be cast into slices
+    /// of these with `Bgra::from_channels` and converted into `Alpha` colors.
+    Bgra { b, g, r, a }
+}
+
+layout! {
+    /// An RGBA color with the channels in the order alpha, red, green and blue
+    ///
+    /// The color has the same memory layout as `[T; 4]`, so ARGB buffers can be cast into slices
+    /// of these with `Argb::from_channels` and converted into `Alpha` colors.
+    Argb { a, r, g, b }
+}
+
+layout! {
+    /// An RGBA color with the channels in the order alpha, blue, green and red
+    ///
+    /// The color has the same memory layout as `[T; 4]`, so ABGR buffers can be cast into slices
+    /// of these with `Abgr::from_channels` and converted into `Alpha` colors.
+    Abgr { a, b, g, r }
+}
+
+impl<T: Channel, S> From<Bgr<T, S>> for RGBColor<T, S> {
+    #[inline]
+    fn from(color: Bgr<T, S>) -> Self {
+        RGBColor::new(color.r, color.g, color.b)
+    }
+}
+
+impl<T, S> From<RGBColor<T, S>> for Bgr<T, S> {
+    #[inline]
+    fn from(color: RGBColor<T, S>) -> Self {
+        let (r, g, b) = color.tuple();
+        Bgr::new(b, g, r)
+    }
+}
+
+/// Implements the conversions between an RGBA layout and `Alpha`
+macro_rules! impl_alpha_layout {
+    ( $( $name:ident ),* ) => { $(
+        impl<T: Channel, S> From<$name<T, S>> for Alpha<RGBColor<T, S>, T> {
+            #[inline]
+            fn from(color: $name<T, S>) -> Self {
+                Alpha::new(RGBColor::new(color.r, color.g, color.b), color.a)
+            }
+        }
+
+        impl<T, S> From<Alpha<RGBColor<T, S>, T>> for $name<T, S> {
+            #[inline]
+            fn from(color: Alpha<RGBColor<T, S>, T>) -> Self {
+                let (r, g, b, a) = color.tuple();
+                $name { r, g, b, a, _space: PhantomData }
+            }
+        }
+    )* };
+}
+
+impl_alpha_layout!(Bgra, Argb, Abgr);
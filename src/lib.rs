@@ -1,30 +1,67 @@
+mod adjust;
 mod alpha;
 mod base;
 mod blend;
+mod cast;
 mod channel;
+mod classify;
+mod cmyk;
+mod contrast;
+mod cvd;
+mod hdr;
 mod hsv;
 mod iter;
+mod layout;
+mod oklab;
+mod packed;
 mod rgb;
-
+mod spectral;
+mod temperature;
+mod xyz;
+mod ycbcr;
+
+pub mod batch;
+pub mod dither;
+pub mod harmony;
+pub mod quantize;
 pub mod space;
+pub mod stats;
 
 #[cfg(test)]
 mod test;
 
 use std::str;
+use std::sync::OnceLock;
 
 use num_traits::NumCast;
 
 pub use self::alpha::*;
+pub use adjust::*;
 pub use base::*;
 pub use blend::*;
+pub use cast::*;
 pub use channel::*;
+pub use classify::*;
+pub use cmyk::*;
+pub use contrast::*;
+pub use cvd::*;
+pub use hdr::*;
 pub use hsv::*;
 pub use iter::*;
+pub use layout::*;
+pub use oklab::*;
+pub use packed::*;
 pub use rgb::*;
+pub use spectral::*;
+pub use temperature::*;
+pub use xyz::*;
+pub use ycbcr::*;
 
 use angle::*;
-use space::{std_gamma_decode, std_gamma_encode, LinearSpace, SRGBSpace};
+use space::{
+    std_gamma_decode, std_gamma_decode_u16, std_gamma_decode_u8, std_gamma_encode,
+    std_gamma_encode_u8, LinearSpace, RGBSpace, SRGBSpace,
+};
 
 /// A trait for colors
 pub trait Color: Sized {
@@ -40,6 +77,20 @@ pub trait Color: Sized {
     fn is_normal(&self) -> bool;
 }
 
+/// A trait for colors that can be converted into linear RGB
+///
+/// Used by the functions that work with any kind of color, like the contrast functions.
+pub trait ToLinear {
+    /// Returns this color in the linear RGB space
+    fn to_linear(&self) -> LinRGBColor;
+}
+
+/// A trait for colors that can be converted from linear RGB
+pub trait FromLinear {
+    /// Creates this color from a linear RGB color
+    fn from_linear(color: LinRGBColor) -> Self;
+}
+
 /// A 96-bit sRGB color with 32-bit floating point channels
 pub type SRGBColor = RGBColor<f32, SRGBSpace>;
 /// A 24-bit sRGB color with 8-bit integer channels
@@ -70,109 +121,32 @@ pub type StdHSVColor = HSVColor<Deg<f32>, f32, SRGBSpace>;
 pub type LinHSVColor = HSVColor<Deg<f32>, f32, LinearSpace>;
 
 /// Classify this color's most prominent shades
+///
+/// Uses the default `Classifier`, see it for more details. The classifier is built once and
+/// reused by every call.
 pub fn shades(color: SRGBColor) -> Vec<(BaseColor, f32)> {
-    use self::BaseColor::*;
-
-    const COLOR_HUES: [(f32, BaseColor); 5] = [
-        (60.0, Yellow),
-        (120.0, Green),
-        (180.0, Cyan),
-        (240.0, Blue),
-        (300.0, Magenta),
-    ];
-
-    // these values below have been picked by what gives nice results
-    // they could be improved
-
-    // how many degrees from the main hue can a shade be
-    const HUE_MARGIN: f32 = 60.0 * 0.75;
-
-    // relative luminance under this value is considered to be just black
-    const BLACK_CUTOFF_LUMINANCE: f32 = 0.005;
-
-    // saturation under this value is considered to be just greyscale without any color
-    const GREYSCALE_SATURATION: f32 = 0.05;
-
-    // borders for the greyscale shades
-    const WHITE_SATURATION: f32 = 0.35;
-    const WHITE_LUMINANCE: f32 = 0.40;
-
-    const GREY_SATURATION: f32 = 0.45;
-    const GREY_LUMINANCE_MAX: f32 = 0.80;
-    const GREY_LUMINANCE_MIN: f32 = 0.03;
-
-    const BLACK_LUMINANCE: f32 = 0.045;
-
-    let mut shades = Vec::with_capacity(3);
-
-    let (h, s, _) = color.hsv::<Deg<f32>>().tuple();
-    let h = h.0;
-
-    let lum: f32 = color.std_decode().relative_luminance().into();
-
-    if lum < BLACK_CUTOFF_LUMINANCE {
-        return vec![(Black, 1.0)];
-    }
-
-    let mut sum = 0.0;
-
-    if s > GREYSCALE_SATURATION {
-        // red is a special case
-        if h >= 360.0 - HUE_MARGIN || h <= 0.0 + HUE_MARGIN {
-            let amount = 1.0 - if h <= 0.0 + HUE_MARGIN { h } else { h - 360.0 } / HUE_MARGIN;
-
-            sum += amount;
-            shades.push((Red, amount));
-        }
-        for (hue, color) in COLOR_HUES.iter() {
-            let dist = (h - hue).abs();
-            if dist <= HUE_MARGIN {
-                let amount = 1.0 - dist / HUE_MARGIN;
-                sum += amount;
-                shades.push((*color, amount));
-            }
-        }
-    }
-
-    if lum <= BLACK_LUMINANCE {
-        sum += 1.0;
-        shades.push((Black, 1.0));
-    } else if lum >= WHITE_LUMINANCE && s <= WHITE_SATURATION {
-        //let amount = 1.0 - (WHITE_SATURATION - s) / WHITE_SATURATION;
-        sum += 1.0;
-        shades.push((White, 1.0));
-    }
-
-    if s <= GREY_SATURATION && lum <= GREY_LUMINANCE_MAX && lum >= GREY_LUMINANCE_MIN {
-        //let amount = 1.0 - (GREY_SATURATION - s) / GREY_SATURATION;
-        sum += 1.0;
-        shades.push((Grey, 1.0));
-    }
-    // sort and normalize
-    shades.sort_unstable_by(|(_, amount), (_, amount2)| amount2.partial_cmp(amount).unwrap());
-
-    return shades
-        .iter_mut()
-        .map(|(color, amount)| (*color, *amount / sum))
-        .collect();
+    static CLASSIFIER: OnceLock<Classifier> = OnceLock::new();
+    CLASSIFIER.get_or_init(Classifier::default).classify(&color)
 }
 
 /// Return the `text` with this color as it's background color using ANSI escapes
 ///
-/// The text itself will be colored white or black, depending on the relative
-/// luminance (or "whiteness") of the color.
+/// The text itself will be colored white or black, depending on which one has the better
+/// contrast ratio against the color.
 pub fn ansi_bgcolor(color: SRGB24Color, text: &str) -> String {
     const CSI: &str = "\u{1B}[";
     let (r, g, b) = color.tuple();
 
-    // color the text as black or white depending on the bg:s lightness
-    let fg = if color.conv::<f32>().std_decode().relative_luminance() < std_gamma_decode(0.5) {
-        format!("{}38;2;255;255;255m", CSI)
-    } else {
-        format!("{}38;2;;;m", CSI)
-    };
+    // color the text as black or white depending on which has the better contrast
+    let candidates = [SRGB24Color::from(BaseColor::White), BaseColor::Black.into()];
+    let (fr, fg, fb) = best_text_color(&color, candidates.iter().copied())
+        .unwrap()
+        .tuple();
 
-    fg + &format!("{}48;2;{};{};{}m{}{0}0m", CSI, r, g, b, text)
+    format!(
+        "{}38;2;{};{};{}m{0}48;2;{};{};{}m{}{0}0m",
+        CSI, fr, fg, fb, r, g, b, text
+    )
 }
 
 #[inline]
@@ -185,6 +159,16 @@ fn cuwf<T: NumCast>(float: f32) -> T {
     T::from(float).unwrap()
 }
 
+#[inline]
+fn cuwd<T: NumCast>(float: f64) -> T {
+    T::from(float).unwrap()
+}
+
+#[inline]
+fn cuwtd<T: NumCast>(n: T) -> f64 {
+    n.to_f64().unwrap()
+}
+
 #[inline]
 fn cuwtf<T: NumCast>(n: T) -> f32 {
     n.to_f32().unwrap()
@@ -0,0 +1,339 @@
+use num_traits::Float;
+
+use crate::*;
+
+/// A color in the perceptually uniform Oklab color space
+///
+/// `l` is the perceived lightness between 0 and 1, `a` is the green-red axis and `b` is the
+/// blue-yellow axis. The `a` and `b` channels are unbounded, but colors inside the sRGB gamut
+/// have them roughly between -0.4 and 0.4.
+///
+/// Oklab is defined relative to the linear sRGB space.
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq)]
+pub struct OklabColor<T> {
+    pub l: T,
+    pub a: T,
+    pub b: T,
+}
+
+/// A color in the Oklab color space in polar coordinates
+///
+/// `l` is the perceived lightness between 0 and 1, `c` is the chroma and `h` is the hue angle.
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq)]
+pub struct OklchColor<H, T> {
+    pub l: T,
+    pub c: T,
+    pub h: H,
+}
+
+impl<T> OklabColor<T> {
+    /// Deconstructs this color into a tuple of it's channels
+    #[inline]
+    pub fn tuple(self) -> (T, T, T) {
+        (self.l, self.a, self.b)
+    }
+}
+
+impl<T: Float> OklabColor<T> {
+    /// Creates a new Oklab color
+    ///
+    /// The lightness is clamped between 0 and 1.
+    pub fn new(l: T, a: T, b: T) -> Self {
+        OklabColor { l, a, b }.normalize()
+    }
+
+    /// Transforms this color into linear RGB
+    ///
+    /// Colors outside of the RGB gamut are clamped into it.
+    pub fn rgb(self) -> RGBColor<T, LinearSpace>
+    where
+        T: Channel,
+    {
+        let (r, g, b) = self.rgb_unclamped();
+        (r, g, b).into()
+    }
+
+    /// Transforms this color into polar coordinates
+    pub fn oklch<H: Channel>(self) -> OklchColor<H, T> {
+        let c = self.a.hypot(self.b);
+        let h = Deg(cuwtf(self.b.atan2(self.a).to_degrees()));
+        OklchColor::new(self.l, c, h.conv::<H>())
+    }
+
+    /// Returns the perceptual distance between the colors
+    ///
+    /// This is the euclidean distance in the Oklab space.
+    pub fn distance(&self, other: &Self) -> T {
+        let (dl, da, db) = (self.l - other.l, self.a - other.a, self.b - other.b);
+        (dl * dl + da * da + db * db).sqrt()
+    }
+
+    /// Returns true if this color is inside the RGB gamut
+    pub fn in_gamut(&self) -> bool {
+        let eps: T = cuw(1e-4);
+        let (r, g, b) = self.rgb_unclamped();
+        [r, g, b].iter().all(|&x| x >= -eps && x <= T::one() + eps)
+    }
+
+    /// Returns the linear RGB channels of this color without clamping them
+    fn rgb_unclamped(self) -> (T, T, T) {
+        let f = |x: f32| -> T { cuw(x) };
+        let (l, a, b) = self.tuple();
+
+        let l_ = l + f(0.396_337_8) * a + f(0.215_803_76) * b;
+        let m_ = l - f(0.105_561_346) * a - f(0.063_854_17) * b;
+        let s_ = l - f(0.089_484_18) * a - f(1.291_485_5) * b;
+
+        let (l, m, s) = (l_ * l_ * l_, m_ * m_ * m_, s_ * s_ * s_);
+
+        (
+            f(4.076_741_7) * l - f(3.307_711_6) * m + f(0.230_969_94) * s,
+            f(-1.268_438) * l + f(2.609_757_4) * m - f(0.341_319_38) * s,
+            f(-0.004_196_086_3) * l - f(0.703_418_6) * m + f(1.707_614_7) * s,
+        )
+    }
+}
+
+impl<T: Float + Channel> RGBColor<T, LinearSpace> {
+    /// Transforms this color into the Oklab space
+    pub fn oklab(self) -> OklabColor<T> {
+        let f = |x: f32| -> T { cuw(x) };
+        let (r, g, b) = self.tuple();
+
+        let l = f(0.412_221_46) * r + f(0.536_332_55) * g + f(0.051_445_995) * b;
+        let m = f(0.211_903_5) * r + f(0.680_699_5) * g + f(0.107_396_96) * b;
+        let s = f(0.088_302_46) * r + f(0.281_718_85) * g + f(0.629_978_7) * b;
+
+        let (l, m, s) = (l.cbrt(), m.cbrt(), s.cbrt());
+
+        OklabColor::new(
+            f(0.210_454_26) * l + f(0.793_617_8) * m - f(0.004_072_047) * s,
+            f(1.977_998_5) * l - f(2.428_592_2) * m + f(0.450_593_7) * s,
+            f(0.025_904_037) * l + f(0.782_771_77) * m - f(0.808_675_77) * s,
+        )
+    }
+
+    /// Transforms this color into the polar Oklch space
+    #[inline]
+    pub fn oklch<H: Channel>(self) -> OklchColor<H, T> {
+        self.oklab().oklch()
+    }
+}
+
+impl<H, T> OklchColor<H, T> {
+    /// Deconstructs this color into a tuple of it's channels
+    #[inline]
+    pub fn tuple(self) -> (T, T, H) {
+        (self.l, self.c, self.h)
+    }
+}
+
+impl<H: Channel, T: Float> OklchColor<H, T> {
+    /// Creates a new Oklch color
+    ///
+    /// The color is normalized on creation.
+    pub fn new(l: T, c: T, h: H) -> Self {
+        OklchColor { l, c, h }.normalize()
+    }
+
+    /// Transforms this color into cartesian Oklab coordinates
+    pub fn oklab(self) -> OklabColor<T> {
+        let h: T = cuw(cuwtf(self.h.conv::<Deg<f32>>()).to_radians());
+        OklabColor::new(self.l, self.c * h.cos(), self.c * h.sin())
+    }
+
+    /// Transforms this color into linear RGB
+    ///
+    /// Colors outside of the RGB gamut are clamped into it.
+    #[inline]
+    pub fn rgb(self) -> RGBColor<T, LinearSpace>
+    where
+        T: Channel,
+    {
+        self.oklab().rgb()
+    }
+
+    /// Returns the largest chroma this color can have while staying inside the RGB gamut
+    ///
+    /// The lightness and hue are kept the same.
+    pub fn max_chroma(&self) -> T
+    where
+        H: Clone,
+    {
+        let with_chroma = |c: T| OklchColor::new(self.l, c, self.h.clone());
+        let (mut low, mut high) = (T::zero(), cuw::<f32, T>(0.5));
+        for _ in 0..24 {
+            let mid = (low + high) / cuw(2.0);
+            if with_chroma(mid).oklab().in_gamut() {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+}
+
+impl<T: Float> Color for OklabColor<T> {
+    /// Clamps the lightness between 0 and 1
+    fn normalize(self) -> Self {
+        OklabColor {
+            l: self.l.max(T::zero()).min(T::one()),
+            ..self
+        }
+    }
+
+    fn is_normal(&self) -> bool {
+        self.l >= T::zero() && self.l <= T::one()
+    }
+}
+
+impl<H: Channel, T: Float> Color for OklchColor<H, T> {
+    /// Clamps the lightness and chroma and wraps the hue
+    ///
+    /// If chroma is zero, hue is set to zero.
+    fn normalize(self) -> Self {
+        let l = self.l.max(T::zero()).min(T::one());
+        let c = self.c.max(T::zero());
+        let h = if c == T::zero() {
+            H::ch_zero()
+        } else {
+            self.h.clamp()
+        };
+        OklchColor { l, c, h }
+    }
+
+    fn is_normal(&self) -> bool {
+        self.l >= T::zero()
+            && self.l <= T::one()
+            && self.c >= T::zero()
+            && self.h.in_range()
+            && (self.c != T::zero() || self.h == H::ch_zero())
+    }
+}
+
+impl<H: Channel + Clone, T: Float + Channel> ColorOps for OklchColor<H, T> {
+    type Opaque = Self;
+
+    /// Moves the lightness of this color towards 1 by the given amount
+    fn lighten(self, amount: f32) -> Self {
+        let amount: T = cuw(Channel::clamp(amount));
+        OklchColor::new(self.l + (T::one() - self.l) * amount, self.c, self.h)
+    }
+
+    /// Moves the lightness of this color towards 0 by the given amount
+    fn darken(self, amount: f32) -> Self {
+        let amount: T = cuw(Channel::clamp(amount));
+        OklchColor::new(self.l - self.l * amount, self.c, self.h)
+    }
+
+    /// Moves the chroma of this color towards the largest chroma inside the RGB gamut
+    fn saturate(self, amount: f32) -> Self {
+        let amount: T = cuw(Channel::clamp(amount));
+        let max = self.max_chroma().max(self.c);
+        OklchColor::new(self.l, self.c + (max - self.c) * amount, self.h)
+    }
+
+    /// Moves the chroma of this color towards 0 by the given amount
+    fn desaturate(self, amount: f32) -> Self {
+        let amount: T = cuw(Channel::clamp(amount));
+        OklchColor::new(self.l, self.c - self.c * amount, self.h)
+    }
+
+    fn shift_hue<A: Angle + Channel>(self, angle: A) -> Self {
+        let h = self.h.conv::<Deg<f32>>() + angle.conv();
+        OklchColor::new(self.l, self.c, h.conv())
+    }
+
+    /// Inverts this color in the sRGB space
+    fn invert(self) -> Self {
+        self.rgb().std_encode().invert().std_decode().oklch()
+    }
+
+    /// Returns the grey with the same perceived lightness
+    fn grayscale(self) -> Self {
+        OklchColor::new(self.l, T::zero(), self.h)
+    }
+
+    fn with_alpha<A: Channel>(self, alpha: A) -> Alpha<Self, A> {
+        Alpha::new(self, alpha)
+    }
+}
+
+impl<T: Float + Channel> ToLinear for OklabColor<T> {
+    #[inline]
+    fn to_linear(&self) -> LinRGBColor {
+        self.rgb().conv()
+    }
+}
+
+impl<H: Channel + Clone, T: Float + Channel> ToLinear for OklchColor<H, T> {
+    #[inline]
+    fn to_linear(&self) -> LinRGBColor {
+        self.clone().rgb().conv()
+    }
+}
+
+impl<T: Float + Channel> FromLinear for OklabColor<T> {
+    #[inline]
+    fn from_linear(color: LinRGBColor) -> Self {
+        color.conv::<T>().oklab()
+    }
+}
+
+impl<H: Channel, T: Float + Channel> FromLinear for OklchColor<H, T> {
+    #[inline]
+    fn from_linear(color: LinRGBColor) -> Self {
+        color.conv::<T>().oklch()
+    }
+}
+
+impl<T: Float + Channel> From<OklabColor<T>> for RGBColor<T, LinearSpace> {
+    fn from(color: OklabColor<T>) -> Self {
+        color.rgb()
+    }
+}
+
+impl<T: Float + Channel> From<RGBColor<T, LinearSpace>> for OklabColor<T> {
+    fn from(color: RGBColor<T, LinearSpace>) -> Self {
+        color.oklab()
+    }
+}
+
+impl<H: Channel, T: Float + Channel> From<OklchColor<H, T>> for RGBColor<T, LinearSpace> {
+    fn from(color: OklchColor<H, T>) -> Self {
+        color.rgb()
+    }
+}
+
+impl<H: Channel, T: Float + Channel> From<RGBColor<T, LinearSpace>> for OklchColor<H, T> {
+    fn from(color: RGBColor<T, LinearSpace>) -> Self {
+        color.oklch()
+    }
+}
+
+impl<T: Float + Channel> From<BaseColor> for OklabColor<T> {
+    fn from(base_color: BaseColor) -> Self {
+        RGBColor::<T, LinearSpace>::from(base_color).oklab()
+    }
+}
+
+impl<H: Channel, T: Float + Channel> From<BaseColor> for OklchColor<H, T> {
+    fn from(base_color: BaseColor) -> Self {
+        RGBColor::<T, LinearSpace>::from(base_color).oklch()
+    }
+}
+
+impl<T: Float> From<(T, T, T)> for OklabColor<T> {
+    fn from(tuple: (T, T, T)) -> Self {
+        let (l, a, b) = tuple;
+        OklabColor::new(l, a, b)
+    }
+}
+
+impl<H2: Into<H>, H: Channel, T: Float> From<(T, T, H2)> for OklchColor<H, T> {
+    fn from(tuple: (T, T, H2)) -> Self {
+        let (l, c, h) = tuple;
+        OklchColor::new(l, c, h.into())
+    }
+}
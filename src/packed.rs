@@ -0,0 +1,331 @@
+use crate::*;
+
+/// Expands a field of `bits` bits into `to` bits by repeating it's bits
+///
+/// This maps zero to zero and the maximum to the maximum, and the original value is got back by
+/// `reduce`.
+fn expand(value: u32, bits: u32, to: u32) -> u32 {
+    let (mut out, mut filled) = (0u64, 0);
+    while filled < to {
+        out = out << bits | value as u64;
+        filled += bits;
+    }
+    (out >> (filled - to)) as u32
+}
+
+/// Reduces a field of `from` bits into `bits` bits, rounding to the nearest value
+fn reduce(value: u32, from: u32, bits: u32) -> u32 {
+    let (from_max, max) = ((1u64 << from) - 1, (1u64 << bits) - 1);
+    ((value as u64 * max + from_max / 2) / from_max) as u32
+}
+
+/// A channel type that can be packed into a field of bits
+trait Field: Sized {
+    fn to_field(self, bits: u32) -> u32;
+    fn from_field(value: u32, bits: u32) -> Self;
+}
+
+macro_rules! impl_int_fields {
+    ( $( $type:ty ),* ) => { $(
+        impl Field for $type {
+            #[inline]
+            fn to_field(self, bits: u32) -> u32 {
+                reduce(self as u32, <$type>::BITS, bits)
+            }
+
+            #[inline]
+            fn from_field(value: u32, bits: u32) -> Self {
+                expand(value, bits, <$type>::BITS) as $type
+            }
+        }
+    )* };
+}
+
+impl_int_fields!(u8, u16);
+
+impl Field for f32 {
+    #[inline]
+    fn to_field(self, bits: u32) -> u32 {
+        let max = ((1u64 << bits) - 1) as f32;
+        (Channel::clamp(self) * max).round() as u32
+    }
+
+    #[inline]
+    fn from_field(value: u32, bits: u32) -> Self {
+        value as f32 / ((1u64 << bits) - 1) as f32
+    }
+}
+
+/// Returns the field of `bits` bits at `shift` in the packed value
+#[inline]
+fn field(packed: u32, shift: u32, bits: u32) -> u32 {
+    packed >> shift & ((1 << bits) - 1)
+}
+
+/// Declares a packed RGB color and it's conversions
+///
+/// The fields of the channels are given as their number of bits and the position of their
+/// lowest bit.
+macro_rules! packed_rgb {
+    (
+        $( #[$meta:meta] )*
+        $name:ident($int:ty): $rb:expr, $rs:expr; $gb:expr, $gs:expr; $bb:expr, $bs:expr
+    ) => {
+        $( #[$meta] )*
+        #[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+        #[repr(transparent)]
+        pub struct $name(pub $int);
+
+        impl $name {
+            /// Packs the raw values of the fields, which are clamped to their number of bits
+            pub fn new(r: $int, g: $int, b: $int) -> Self {
+                let clamp = |value: $int, bits: u32| (value as u32).min((1 << bits) - 1);
+                $name((clamp(r, $rb) << $rs | clamp(g, $gb) << $gs | clamp(b, $bb) << $bs) as $int)
+            }
+
+            /// Returns the raw values of the fields
+            pub fn fields(self) -> ($int, $int, $int) {
+                let packed = self.0 as u32;
+                (
+                    field(packed, $rs, $rb) as $int,
+                    field(packed, $gs, $gb) as $int,
+                    field(packed, $bs, $bb) as $int,
+                )
+            }
+        }
+
+        impl_packed_rgb!($name($int): $rb, $rs; $gb, $gs; $bb, $bs => u8, u16, f32);
+    };
+}
+
+macro_rules! impl_packed_rgb {
+    ( $name:ident($int:ty): $rb:expr, $rs:expr; $gb:expr, $gs:expr; $bb:expr, $bs:expr => $( $type:ty ),* ) => { $(
+        impl<S> From<RGBColor<$type, S>> for $name {
+            fn from(color: RGBColor<$type, S>) -> Self {
+                let (r, g, b) = color.tuple();
+                $name((r.to_field($rb) << $rs | g.to_field($gb) << $gs | b.to_field($bb) << $bs) as $int)
+            }
+        }
+
+        impl<S> From<$name> for RGBColor<$type, S> {
+            fn from(color: $name) -> Self {
+                let packed = color.0 as u32;
+                let channel = |shift, bits| <$type>::from_field(field(packed, shift, bits), bits);
+                RGBColor::new(channel($rs, $rb), channel($gs, $gb), channel($bs, $bb))
+            }
+        }
+    )* };
+}
+
+/// Declares a packed RGBA color and it's conversions
+///
+/// The fields of the channels are given as their number of bits and the position of their
+/// lowest bit.
+macro_rules! packed_rgba {
+    (
+        $( #[$meta:meta] )*
+        $name:ident($int:ty): $rb:expr, $rs:expr; $gb:expr, $gs:expr; $bb:expr, $bs:expr; $ab:expr, $as:expr
+    ) => {
+        $( #[$meta] )*
+        #[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+        #[repr(transparent)]
+        pub struct $name(pub $int);
+
+        impl $name {
+            /// Packs the raw values of the fields, which are clamped to their number of bits
+            pub fn new(r: $int, g: $int, b: $int, a: $int) -> Self {
+                let clamp = |value: $int, bits: u32| (value as u32).min((1 << bits) - 1);
+                $name((clamp(r, $rb) << $rs
+                    | clamp(g, $gb) << $gs
+                    | clamp(b, $bb) << $bs
+                    | clamp(a, $ab) << $as) as $int)
+            }
+
+            /// Returns the raw values of the fields
+            pub fn fields(self) -> ($int, $int, $int, $int) {
+                let packed = self.0 as u32;
+                (
+                    field(packed, $rs, $rb) as $int,
+                    field(packed, $gs, $gb) as $int,
+                    field(packed, $bs, $bb) as $int,
+                    field(packed, $as, $ab) as $int,
+                )
+            }
+        }
+
+        impl_packed_rgba!($name($int): $rb, $rs; $gb, $gs; $bb, $bs; $ab, $as => u8, u16, f32);
+    };
+}
+
+macro_rules! impl_packed_rgba {
+    ( $name:ident($int:ty): $rb:expr, $rs:expr; $gb:expr, $gs:expr; $bb:expr, $bs:expr; $ab:expr, $as:expr => $( $type:ty ),* ) => { $(
+        impl<S> From<Alpha<RGBColor<$type, S>, $type>> for $name {
+            fn from(color: Alpha<RGBColor<$type, S>, $type>) -> Self {
+                let (r, g, b, a) = color.tuple();
+                $name((r.to_field($rb) << $rs
+                    | g.to_field($gb) << $gs
+                    | b.to_field($bb) << $bs
+                    | a.to_field($ab) << $as) as $int)
+            }
+        }
+
+        impl<S> From<$name> for Alpha<RGBColor<$type, S>, $type> {
+            fn from(color: $name) -> Self {
+                let packed = color.0 as u32;
+                let channel = |shift, bits| <$type>::from_field(field(packed, shift, bits), bits);
+                Alpha::new(
+                    RGBColor::new(channel($rs, $rb), channel($gs, $gb), channel($bs, $bb)),
+                    channel($as, $ab),
+                )
+            }
+        }
+    )* };
+}
+
+packed_rgb! {
+    /// A 16-bit RGB color with 5 bits of red, 6 bits of green and 5 bits of blue
+    ///
+    /// Red is in the most significant bits and blue in the least significant.
+    RGB565(u16): 5, 11; 6, 5; 5, 0
+}
+
+packed_rgb! {
+    /// A 16-bit RGB color with 5 bits for every channel
+    ///
+    /// Red is in bits 10 to 14 and blue in the least significant bits. The most significant bit
+    /// is unused and always zero.
+    RGB555(u16): 5, 10; 5, 5; 5, 0
+}
+
+packed_rgba! {
+    /// A 16-bit RGBA color with 4 bits for every channel
+    ///
+    /// Red is in the most significant bits and alpha in the least significant.
+    RGBA4444(u16): 4, 12; 4, 8; 4, 4; 4, 0
+}
+
+packed_rgba! {
+    /// A 32-bit RGBA color with 10 bits for the color channels and 2 bits for the alpha
+    ///
+    /// Red is in the least significant bits and alpha in the most significant, like in the
+    /// `R10G10B10A2` format of the graphics APIs.
+    RGB10A2(u32): 10, 0; 10, 10; 10, 20; 2, 30
+}
+
+packed_rgba! {
+    /// A 32-bit RGBA color with 8-bit channels, blue in the most significant byte
+    ///
+    /// This is the `0xBBGGRRAA` format.
+    BGRA8(u32): 8, 8; 8, 16; 8, 24; 8, 0
+}
+
+packed_rgba! {
+    /// A 32-bit RGBA color with 8-bit channels, alpha in the most significant byte
+    ///
+    /// This is the `0xAARRGGBB` format used by many windowing systems.
+    ARGB8(u32): 8, 16; 8, 8; 8, 0; 8, 24
+}
+
+packed_rgba! {
+    /// A 32-bit RGBA color with 8-bit channels, alpha in the most significant byte and red in the
+    /// least significant
+    ///
+    /// This is the `0xAABBGGRR` format. On little-endian machines the bytes are in the order red,
+    /// green, blue and alpha in memory.
+    ABGR8(u32): 8, 0; 8, 8; 8, 16; 8, 24
+}
+
+/// Converts a float into an unsigned float with 5 exponent bits and `mantissa` mantissa bits
+///
+/// Negative values become zero and too large values the largest finite value. The mantissa is
+/// rounded to the nearest value, ties to even.
+fn to_small_float(x: f32, mantissa: u32) -> u32 {
+    const BIAS: i32 = 15;
+    let (inf, max) = (31 << mantissa, (31 << mantissa) - 1);
+    if x.is_nan() {
+        return inf | 1;
+    } else if x == f32::INFINITY {
+        return inf;
+    } else if x <= 0.0 {
+        return 0;
+    }
+
+    let bits = x.to_bits();
+    let exponent = (bits >> 23) as i32 - 127 + BIAS;
+    if exponent >= 31 {
+        return max;
+    } else if exponent <= 0 {
+        // subnormal, scale the smallest subnormal to 1
+        let scaled = x * 2f32.powi(BIAS - 1 + mantissa as i32);
+        let (truncated, rest) = (scaled.floor(), scaled - scaled.floor());
+        let round_up = rest > 0.5 || rest == 0.5 && truncated % 2.0 == 1.0;
+        return truncated as u32 + round_up as u32;
+    }
+
+    let shift = 23 - mantissa;
+    let (truncated, rest) = ((bits & 0x7f_ffff) >> shift, bits & ((1 << shift) - 1));
+    let half = 1 << (shift - 1);
+    let mut value = (exponent as u32) << mantissa | truncated;
+    if rest > half || rest == half && truncated & 1 == 1 {
+        // carries into the exponent if the mantissa overflows
+        value += 1;
+    }
+    value.min(max)
+}
+
+/// Converts an unsigned float with 5 exponent bits and `mantissa` mantissa bits into a float
+fn from_small_float(value: u32, mantissa: u32) -> f32 {
+    const BIAS: i32 = 15;
+    let (exponent, fraction) = (value >> mantissa, value & ((1 << mantissa) - 1));
+    let scale = (1u32 << mantissa) as f32;
+    match exponent {
+        0 => fraction as f32 / scale * 2f32.powi(1 - BIAS),
+        31 if fraction == 0 => f32::INFINITY,
+        31 => f32::NAN,
+        _ => (1.0 + fraction as f32 / scale) * 2f32.powi(exponent as i32 - BIAS),
+    }
+}
+
+/// A 32-bit RGB color with unsigned floating point channels
+///
+/// Red and green have 11 bits with a 6-bit mantissa, and blue has 10 bits with a 5-bit mantissa,
+/// all with a 5-bit exponent. Red is in the least significant bits, like in the `R11G11B10F`
+/// format of the graphics APIs.
+///
+/// The channels can be larger than 1, up to 65024 for red and green and 64512 for blue, so this
+/// is mostly used for linear HDR colors. Negative channels become zero when packing.
+#[derive(Debug, Copy, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[repr(transparent)]
+pub struct R11G11B10F(pub u32);
+
+impl R11G11B10F {
+    /// Packs the channels without clamping them to the range from 0 to 1
+    pub fn pack(r: f32, g: f32, b: f32) -> Self {
+        R11G11B10F(to_small_float(r, 6) | to_small_float(g, 6) << 11 | to_small_float(b, 5) << 22)
+    }
+
+    /// Unpacks the channels without clamping them to the range from 0 to 1
+    pub fn unpack(self) -> (f32, f32, f32) {
+        (
+            from_small_float(field(self.0, 0, 11), 6),
+            from_small_float(field(self.0, 11, 11), 6),
+            from_small_float(field(self.0, 22, 10), 5),
+        )
+    }
+}
+
+impl<S> From<RGBColor<f32, S>> for R11G11B10F {
+    fn from(color: RGBColor<f32, S>) -> Self {
+        let (r, g, b) = color.tuple();
+        R11G11B10F::pack(r, g, b)
+    }
+}
+
+impl<S> From<R11G11B10F> for RGBColor<f32, S> {
+    /// Unpacks the color, clamping the channels
+    ///
+    /// Use `R11G11B10F::unpack` to keep the channels larger than 1.
+    fn from(color: R11G11B10F) -> Self {
+        color.unpack().into()
+    }
+}
@@ -0,0 +1,385 @@
+//! Palette extraction by color quantization
+//!
+//! All the algorithms take a slice of pixels and return at most `count` colors with their
+//! weights, which tell how much of the image each color represents. The weights sum up to 1 and
+//! the colors are sorted from the largest weight to the smallest. Fewer colors are returned if
+//! the image doesn't have enough different colors, and an empty image gives no colors.
+//!
+//! The colors are averaged in the Oklab space, so the palette colors look like the pixels they
+//! represent. Transparent pixels count less by their alpha.
+
+use crate::*;
+
+/// A pixel that can be quantized
+pub trait Pixel {
+    /// Returns the opaque color of this pixel
+    fn color(&self) -> SRGB24Color;
+
+    /// Returns how much this pixel counts, between 0 and 1
+    fn weight(&self) -> f32;
+}
+
+impl Pixel for SRGB24Color {
+    #[inline]
+    fn color(&self) -> SRGB24Color {
+        *self
+    }
+
+    #[inline]
+    fn weight(&self) -> f32 {
+        1.0
+    }
+}
+
+impl Pixel for SRGBA32Color {
+    #[inline]
+    fn color(&self) -> SRGB24Color {
+        self.color
+    }
+
+    #[inline]
+    fn weight(&self) -> f32 {
+        self.alpha.conv()
+    }
+}
+
+/// A distinct color of the image with the total weight of it's pixels
+#[derive(Debug, Copy, Clone)]
+struct Entry {
+    rgb: (u8, u8, u8),
+    lab: [f32; 3],
+    weight: f32,
+}
+
+/// Collects the distinct colors of the pixels, sorted by their RGB values
+fn histogram<P: Pixel>(pixels: &[P]) -> Vec<Entry> {
+    let mut colors: Vec<((u8, u8, u8), f32)> = pixels
+        .iter()
+        .map(|p| (p.color().tuple(), p.weight()))
+        .filter(|(_, weight)| *weight > 0.0)
+        .collect();
+    colors.sort_by_key(|(rgb, _)| *rgb);
+
+    let mut entries: Vec<Entry> = Vec::new();
+    for (rgb, weight) in colors {
+        match entries.last_mut() {
+            Some(last) if last.rgb == rgb => last.weight += weight,
+            _ => {
+                let lab = SRGB24Color::from(rgb).to_linear().oklab();
+                entries.push(Entry {
+                    rgb,
+                    lab: [lab.l, lab.a, lab.b],
+                    weight,
+                });
+            }
+        }
+    }
+    entries
+}
+
+/// Accumulates the weighted mean of Oklab colors
+#[derive(Debug, Copy, Clone, Default)]
+struct Mean {
+    sum: [f64; 3],
+    weight: f64,
+}
+
+impl Mean {
+    fn add(&mut self, lab: [f32; 3], weight: f32) {
+        for (sum, x) in self.sum.iter_mut().zip(lab.iter()) {
+            *sum += (x * weight) as f64;
+        }
+        self.weight += weight as f64;
+    }
+
+    fn lab(&self) -> [f32; 3] {
+        let w = self.weight.max(f64::MIN_POSITIVE);
+        [
+            (self.sum[0] / w) as f32,
+            (self.sum[1] / w) as f32,
+            (self.sum[2] / w) as f32,
+        ]
+    }
+}
+
+/// Turns the means into the sorted palette with normalized weights
+fn palette<I: IntoIterator<Item = Mean>>(means: I) -> Vec<(SRGB24Color, f32)> {
+    let means: Vec<Mean> = means.into_iter().filter(|m| m.weight > 0.0).collect();
+    let total: f64 = means.iter().map(|m| m.weight).sum();
+
+    let mut palette: Vec<(SRGB24Color, f32)> = means
+        .iter()
+        .map(|m| {
+            let [l, a, b] = m.lab();
+            let color = SRGB24Color::from_linear(OklabColor::new(l, a, b).rgb());
+            (color, (m.weight / total) as f32)
+        })
+        .collect();
+    palette.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+    palette
+}
+
+fn distance(a: &[f32; 3], b: &[f32; 3]) -> f32 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+/// Extracts a palette with the median cut algorithm
+///
+/// The colors are split into boxes in the Oklab space. The box with the longest side is split
+/// in two at the weighted median of that side, until there are `count` boxes.
+pub fn median_cut<P: Pixel>(pixels: &[P], count: usize) -> Vec<(SRGB24Color, f32)> {
+    // returns the longest axis of the box and it's length
+    fn longest_axis(entries: &[Entry]) -> (usize, f32) {
+        (0..3)
+            .map(|axis| {
+                let values = entries.iter().map(|e| e.lab[axis]);
+                let min = values.clone().fold(f32::INFINITY, f32::min);
+                let max = values.fold(f32::NEG_INFINITY, f32::max);
+                (axis, max - min)
+            })
+            .fold((0, 0.0), |(axis, length), side| {
+                if side.1 > length {
+                    side
+                } else {
+                    (axis, length)
+                }
+            })
+    }
+
+    let entries = histogram(pixels);
+    if entries.is_empty() || count == 0 {
+        return Vec::new();
+    }
+
+    let mut boxes = vec![entries];
+    while boxes.len() < count {
+        // find the box with the longest side that can still be split
+        let mut best: Option<(usize, usize, f32)> = None;
+        for (i, entries) in boxes.iter().enumerate() {
+            let (axis, length) = longest_axis(entries);
+            match best {
+                Some((_, _, best_length)) if best_length >= length => {}
+                _ if length > 0.0 => best = Some((i, axis, length)),
+                _ => {}
+            }
+        }
+        let (index, axis) = match best {
+            Some((index, axis, _)) => (index, axis),
+            None => break,
+        };
+
+        let mut entries = boxes.swap_remove(index);
+        entries.sort_by(|a, b| a.lab[axis].partial_cmp(&b.lab[axis]).unwrap());
+
+        // split at the weighted median, keeping at least one color on both sides
+        let half = entries.iter().map(|e| e.weight).sum::<f32>() / 2.0;
+        let mut acc = 0.0;
+        let split = entries
+            .iter()
+            .position(|e| {
+                acc += e.weight;
+                acc >= half
+            })
+            .unwrap_or(0)
+            .max(1)
+            .min(entries.len() - 1);
+
+        let upper = entries.split_off(split);
+        boxes.push(entries);
+        boxes.push(upper);
+    }
+
+    palette(boxes.iter().map(|entries| {
+        let mut mean = Mean::default();
+        for e in entries {
+            mean.add(e.lab, e.weight);
+        }
+        mean
+    }))
+}
+
+/// Extracts a palette with the octree algorithm
+///
+/// The colors are put into an octree by the bits of their RGB channels, and the leaves with the
+/// smallest weights are merged into their parents, starting from the deepest level, until there
+/// are at most `count` leaves. Merging several leaves at once can leave the palette a few colors
+/// smaller than `count`.
+pub fn octree<P: Pixel>(pixels: &[P], count: usize) -> Vec<(SRGB24Color, f32)> {
+    struct Node {
+        children: [Option<usize>; 8],
+        depth: usize,
+        mean: Mean,
+    }
+
+    let entries = histogram(pixels);
+    if entries.is_empty() || count == 0 {
+        return Vec::new();
+    }
+
+    let mut nodes = vec![Node {
+        children: [None; 8],
+        depth: 0,
+        mean: Mean::default(),
+    }];
+
+    // every node holds the mean of all the colors below it
+    for e in &entries {
+        let (r, g, b) = e.rgb;
+        let mut node = 0;
+        nodes[0].mean.add(e.lab, e.weight);
+        for depth in 0..8 {
+            let bit = 7 - depth;
+            let octant = ((r >> bit & 1) << 2 | (g >> bit & 1) << 1 | (b >> bit & 1)) as usize;
+            node = match nodes[node].children[octant] {
+                Some(child) => child,
+                None => {
+                    nodes.push(Node {
+                        children: [None; 8],
+                        depth: depth + 1,
+                        mean: Mean::default(),
+                    });
+                    let child = nodes.len() - 1;
+                    nodes[node].children[octant] = Some(child);
+                    child
+                }
+            };
+            nodes[node].mean.add(e.lab, e.weight);
+        }
+    }
+
+    // reduce the tree from the bottom up, the lightest nodes of each level first
+    let mut leaves = entries.len();
+    for depth in (0..8).rev() {
+        let mut level: Vec<usize> = (0..nodes.len())
+            .filter(|&i| nodes[i].depth == depth)
+            .collect();
+        level.sort_by(|&a, &b| {
+            let (a, b) = (nodes[a].mean.weight, nodes[b].mean.weight);
+            a.partial_cmp(&b).unwrap()
+        });
+
+        for i in level {
+            if leaves <= count {
+                break;
+            }
+            let children = nodes[i].children.iter().filter(|c| c.is_some()).count();
+            nodes[i].children = [None; 8];
+            leaves -= children - 1;
+        }
+    }
+
+    // collect the leaves that are still reachable from the root
+    let mut means = Vec::new();
+    let mut stack = vec![0];
+    while let Some(i) = stack.pop() {
+        let children: Vec<usize> = nodes[i].children.iter().flatten().copied().collect();
+        if children.is_empty() {
+            means.push(nodes[i].mean);
+        }
+        stack.extend(children);
+    }
+
+    palette(means)
+}
+
+/// A small deterministic random number generator (SplitMix64)
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random number in `0.0..1.0`
+    fn next_f64(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Extracts a palette with the k-means clustering algorithm in the Oklab space
+///
+/// The initial cluster centers are picked with k-means++ using the random seed, so the same
+/// seed always gives the same palette. The clusters are refined until they don't change or
+/// `iterations` rounds have been made.
+pub fn kmeans<P: Pixel>(
+    pixels: &[P],
+    count: usize,
+    iterations: usize,
+    seed: u64,
+) -> Vec<(SRGB24Color, f32)> {
+    let entries = histogram(pixels);
+    if entries.is_empty() || count == 0 {
+        return Vec::new();
+    }
+    let mut rng = Rng(seed);
+
+    // k-means++: pick the centers far from the previous ones with a higher probability
+    let mut centers: Vec<[f32; 3]> = Vec::with_capacity(count);
+    let mut nearest = vec![f32::INFINITY; entries.len()];
+    while centers.len() < count.min(entries.len()) {
+        let weights: Vec<f64> = if centers.is_empty() {
+            entries.iter().map(|e| e.weight as f64).collect()
+        } else {
+            entries
+                .iter()
+                .zip(nearest.iter())
+                .map(|(e, d)| (e.weight * d) as f64)
+                .collect()
+        };
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            // all the colors are already centers
+            break;
+        }
+
+        let mut target = rng.next_f64() * total;
+        let index = weights
+            .iter()
+            .position(|w| {
+                target -= w;
+                target < 0.0
+            })
+            .unwrap_or(entries.len() - 1);
+
+        let center = entries[index].lab;
+        for (d, e) in nearest.iter_mut().zip(entries.iter()) {
+            *d = d.min(distance(&e.lab, &center));
+        }
+        centers.push(center);
+    }
+
+    let mut assignment = vec![usize::MAX; entries.len()];
+    let mut means = Vec::new();
+    for _ in 0..iterations.max(1) {
+        let mut changed = false;
+        for (a, e) in assignment.iter_mut().zip(entries.iter()) {
+            let closest = (0..centers.len())
+                .min_by(|&i, &j| {
+                    let (di, dj) = (distance(&e.lab, &centers[i]), distance(&e.lab, &centers[j]));
+                    di.partial_cmp(&dj).unwrap()
+                })
+                .unwrap();
+            changed |= *a != closest;
+            *a = closest;
+        }
+
+        means = vec![Mean::default(); centers.len()];
+        for (&a, e) in assignment.iter().zip(entries.iter()) {
+            means[a].add(e.lab, e.weight);
+        }
+        if !changed {
+            break;
+        }
+        for (center, mean) in centers.iter_mut().zip(means.iter()) {
+            if mean.weight > 0.0 {
+                *center = mean.lab();
+            }
+        }
+    }
+
+    palette(means)
+}
@@ -1,7 +1,8 @@
 use std::fmt;
 use std::marker::PhantomData;
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
 
+use num_traits::ops::saturating::{SaturatingAdd, SaturatingMul, SaturatingSub};
 use num_traits::Float;
 
 use crate::*;
@@ -9,7 +10,11 @@ use crate::*;
 /// An RGB color
 ///
 /// `T` is the type of this color's channels, and `S` is this color's colorspace.
+///
+/// The color has the same memory layout as `[T; 3]`, so slices of channels can be cast into
+/// slices of colors with `RGBColor::from_channels`.
 #[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
+#[repr(C)]
 pub struct RGBColor<T, S> {
     pub r: T,
     pub g: T,
@@ -18,6 +23,20 @@ pub struct RGBColor<T, S> {
 }
 
 impl<T, S> RGBColor<T, S> {
+    /// Creates a new RGB-color with the given values without clamping them
+    ///
+    /// Useful for HDR colors whose channels go beyond 1. Note that most of the methods expect
+    /// the channels to be in their range.
+    #[inline]
+    pub fn new_unchecked(r: T, g: T, b: T) -> Self {
+        RGBColor {
+            r,
+            g,
+            b,
+            _space: PhantomData,
+        }
+    }
+
     /// Applies the given function to all color channels.
     #[inline]
     pub fn map<U, F: Fn(T) -> U>(self, fun: F) -> RGBColor<U, S> {
@@ -56,6 +75,15 @@ impl<T: Channel, S> RGBColor<T, S> {
         .map(Channel::clamp)
     }
 
+    /// Creates a new RGB-color, or returns an error if any of the values is out of range
+    pub fn try_new(r: T, g: T, b: T) -> Result<Self, ChannelRangeError> {
+        Ok(RGBColor::new_unchecked(
+            check_range(r, "red")?,
+            check_range(g, "green")?,
+            check_range(b, "blue")?,
+        ))
+    }
+
     /// Converts the channels of this color into another type
     #[inline]
     pub fn conv<U: Channel>(self) -> RGBColor<U, S> {
@@ -135,31 +163,47 @@ impl<S> RGBColor<u8, S> {
     }
 }
 
+/// Converts RGB channels between 0 and 1 into the HSV hue, saturation and value
+///
+/// The hue isn't wrapped, it can be negative.
+#[inline]
+pub(crate) fn rgb_to_hsv(r: f32, g: f32, b: f32) -> (Deg<f32>, f32, f32) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let value = max;
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+    let hue = Deg(60.0
+        * if delta == 0.0 {
+            0.0
+        } else if max == r {
+            ((g - b) / delta) % 6.0
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        });
+
+    (hue, saturation, value)
+}
+
 impl<T: Channel, S> RGBColor<T, S> {
     pub fn hsv<H: Channel>(self) -> HSVColor<H, T, S> {
         let (r, g, b) = self.map(Channel::conv::<f32>).tuple();
-
-        let max = r.max(g).max(b);
-        let min = r.min(g).min(b);
-        let delta = max - min;
-
-        let value = max;
-        let saturation = if max == 0.0 { 0.0 } else { delta / max };
-        let hue = Deg(60.0
-            * if delta == 0.0 {
-                0.0
-            } else if max == r {
-                ((g - b) / delta) % 6.0
-            } else if max == g {
-                (b - r) / delta + 2.0
-            } else {
-                (r - g) / delta + 4.0
-            });
-
+        let (hue, saturation, value) = rgb_to_hsv(r, g, b);
         HSVColor::new(hue.conv::<H>(), saturation.conv(), value.conv())
     }
 }
 
+impl<T: Float + Channel, S: RGBSpace> RGBColor<T, S> {
+    /// Decodes this color into the linear color space using the transfer function of it's space
+    #[inline]
+    pub fn decode(self) -> RGBColor<T, LinearSpace> {
+        self.map(S::decode).tuple().into()
+    }
+}
+
 impl<T: Float + Channel> RGBColor<T, SRGBSpace> {
     /// Gamma decodes this color channel value into the linear color space
     #[inline]
@@ -175,6 +219,12 @@ impl<T: Float + Channel> RGBColor<T, LinearSpace> {
         self.map(std_gamma_encode).tuple().into()
     }
 
+    /// Encodes this linear color into the color space `S`
+    #[inline]
+    pub fn encode<S: RGBSpace>(self) -> RGBColor<T, S> {
+        self.map(S::encode).tuple().into()
+    }
+
     /// Returns the relative luminance of this color between 0 and 1.
     ///
     /// Tells the whiteness of the color as perceived by humans.
@@ -189,6 +239,20 @@ impl<T: Float + Channel> RGBColor<T, LinearSpace> {
     }
 }
 
+impl<T: Channel + Clone, S: RGBSpace> ToLinear for RGBColor<T, S> {
+    #[inline]
+    fn to_linear(&self) -> LinRGBColor {
+        self.clone().conv::<f32>().decode()
+    }
+}
+
+impl<T: Channel, S: RGBSpace> FromLinear for RGBColor<T, S> {
+    #[inline]
+    fn from_linear(color: LinRGBColor) -> Self {
+        color.encode::<S>().conv()
+    }
+}
+
 impl<T: Channel, S> Default for RGBColor<T, S> {
     fn default() -> Self {
         RGBColor::new(T::ch_zero(), T::ch_zero(), T::ch_zero())
@@ -217,17 +281,23 @@ impl<T: Channel> From<BaseColor> for RGBColor<T, SRGBSpace> {
         let cm = || T::ch_mid();
         let c1 = || T::ch_max();
 
+        let cf = |x: f32| x.conv::<T>();
+
         let f = &RGBColor::new;
         match base_color {
             Black => f(c0(), c0(), c0()),
             Grey => f(cm(), cm(), cm()),
             White => f(c1(), c1(), c1()),
             Red => f(c1(), c0(), c0()),
+            Orange => f(c1(), cm(), c0()),
             Yellow => f(c1(), c1(), c0()),
             Green => f(c0(), c1(), c0()),
             Cyan => f(c0(), c1(), c1()),
             Blue => f(c0(), c0(), c1()),
+            Purple => f(cm(), c0(), c1()),
             Magenta => f(c1(), c0(), c1()),
+            Pink => f(c1(), cf(0.75), cf(0.8)),
+            Brown => f(cm(), cf(0.25), c0()),
         }
     }
 }
@@ -241,6 +311,36 @@ impl<T: Channel> From<BaseColor> for RGBColor<T, LinearSpace> {
     }
 }
 
+impl From<RGBColor<u8, SRGBSpace>> for RGBColor<f32, LinearSpace> {
+    /// Decodes the color with a lookup table
+    ///
+    /// Gives the same result as `color.conv::<f32>().std_decode()`.
+    #[inline]
+    fn from(color: RGBColor<u8, SRGBSpace>) -> Self {
+        color.map(std_gamma_decode_u8).tuple().into()
+    }
+}
+
+impl From<RGBColor<u16, SRGBSpace>> for RGBColor<f32, LinearSpace> {
+    /// Decodes the color with a lookup table
+    ///
+    /// Gives the same result as `color.conv::<f32>().std_decode()`.
+    #[inline]
+    fn from(color: RGBColor<u16, SRGBSpace>) -> Self {
+        color.map(std_gamma_decode_u16).tuple().into()
+    }
+}
+
+impl From<RGBColor<f32, LinearSpace>> for RGBColor<u8, SRGBSpace> {
+    /// Encodes the color with lookup tables
+    ///
+    /// Gives the same result as `color.std_encode().conv::<u8>()`.
+    #[inline]
+    fn from(color: RGBColor<f32, LinearSpace>) -> Self {
+        color.map(std_gamma_encode_u8).tuple().into()
+    }
+}
+
 impl<T: Channel, S> From<(T, T, T)> for RGBColor<T, S> {
     fn from(tuple: (T, T, T)) -> Self {
         let (r, g, b) = tuple;
@@ -327,6 +427,95 @@ where
     }
 }
 
+macro_rules! impl_assign_ops {
+    ( $( $trait:ident, $fun:ident, $op_trait:ident, $op_fun:ident );* ) => { $(
+        impl<T> $trait for RGBColor<T, LinearSpace>
+        where
+            T: Channel + $op_trait<Output = T> + Copy,
+        {
+            fn $fun(&mut self, rhs: Self) {
+                *self = $op_trait::$op_fun(*self, rhs);
+            }
+        }
+    )* };
+}
+
+impl_assign_ops!(
+    AddAssign, add_assign, Add, add;
+    SubAssign, sub_assign, Sub, sub;
+    MulAssign, mul_assign, Mul, mul;
+    DivAssign, div_assign, Div, div
+);
+
+impl<T> MulAssign<T> for RGBColor<T, LinearSpace>
+where
+    T: Channel + Mul<Output = T> + Copy,
+{
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T> DivAssign<T> for RGBColor<T, LinearSpace>
+where
+    T: Channel + Div<Output = T> + Copy,
+{
+    fn div_assign(&mut self, rhs: T) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T> Neg for RGBColor<T, LinearSpace>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    /// Negates all the channels of this color
+    ///
+    /// Unlike the other operators the result is not clamped, because clamping would turn every
+    /// color into black. The negated color is meant to be used in further arithmetic, like
+    /// `a + -b`, which is clamped as usual. On it's own it's only normal if the color was black.
+    fn neg(self) -> Self::Output {
+        self.map(Neg::neg)
+    }
+}
+
+impl<T: Channel + SaturatingAdd + SaturatingSub + SaturatingMul> RGBColor<T, LinearSpace> {
+    /// Adds the channels of the colors together, saturating at the numeric bounds
+    #[inline]
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        (
+            self.r.saturating_add(&rhs.r),
+            self.g.saturating_add(&rhs.g),
+            self.b.saturating_add(&rhs.b),
+        )
+            .into()
+    }
+
+    /// Subtracts the channels of the colors, saturating at the numeric bounds
+    #[inline]
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        (
+            self.r.saturating_sub(&rhs.r),
+            self.g.saturating_sub(&rhs.g),
+            self.b.saturating_sub(&rhs.b),
+        )
+            .into()
+    }
+
+    /// Multiplies the channels of the colors, saturating at the numeric bounds
+    #[inline]
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        (
+            self.r.saturating_mul(&rhs.r),
+            self.g.saturating_mul(&rhs.g),
+            self.b.saturating_mul(&rhs.b),
+        )
+            .into()
+    }
+}
+
 impl<S> fmt::Display for RGBColor<u8, S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:3}, {:3}, {:3}", self.r, self.g, self.b)
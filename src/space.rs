@@ -1,10 +1,16 @@
 //! Colorspaces and conversions between them
 
-use crate::cuw;
+use std::convert::TryInto;
+use std::sync::OnceLock;
+
+use crate::{cuw, Channel};
 use num_traits::Float;
 
 /// The sRGB gamma value, used for sRGB decoding and encoding
-pub const STD_GAMMA: f32 = 2.4;
+pub const STD_GAMMA: f32 = GAMMA as f32;
+
+/// The sRGB gamma value in f64, to keep the precision of f64 channels
+const GAMMA: f64 = 2.4;
 
 /// Marker struct for the sRGB color space
 #[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
@@ -14,22 +20,284 @@ pub struct SRGBSpace;
 #[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
 pub struct LinearSpace;
 
+/// Marker struct for the Rec. 2100 color space with the Perceptual Quantizer transfer function
+///
+/// The space has the wide gamut of Rec. 2020, so it's linear colors aren't in the linear space
+/// of sRGB. It doesn't implement `RGBSpace`, the colors are converted with
+/// `RGBColor::from_hdr` and `RGBColor::to_hdr` instead.
+#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub struct Rec2100PQSpace;
+
+/// Marker struct for the Rec. 2100 color space with the Hybrid Log-Gamma transfer function
+///
+/// See `Rec2100PQSpace` for the conversions.
+#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub struct Rec2100HLGSpace;
+
+/// A trait for RGB color spaces
+///
+/// Defines the transfer functions between this space and the linear space.
+pub trait RGBSpace {
+    /// Decodes a color channel of this space into the linear space
+    fn decode<T: Float>(encoded: T) -> T;
+
+    /// Encodes a linear color channel into this space
+    fn encode<T: Float>(linear: T) -> T;
+}
+
+impl RGBSpace for SRGBSpace {
+    #[inline]
+    fn decode<T: Float>(encoded: T) -> T {
+        std_gamma_decode(encoded)
+    }
+
+    #[inline]
+    fn encode<T: Float>(linear: T) -> T {
+        std_gamma_encode(linear)
+    }
+}
+
+impl RGBSpace for LinearSpace {
+    #[inline]
+    fn decode<T: Float>(encoded: T) -> T {
+        encoded
+    }
+
+    #[inline]
+    fn encode<T: Float>(linear: T) -> T {
+        linear
+    }
+}
+
 /// Gamma encode a linear color channel into the sRGB space
 pub fn std_gamma_encode<T: Float>(linear: T) -> T {
-    const SRGB_CUTOFF: f32 = 0.0031308;
+    // the constants are in f64 to keep the precision of f64 channels
+    const SRGB_CUTOFF: f64 = 0.0031308;
     if linear <= cuw(SRGB_CUTOFF) {
         linear * cuw(12.92)
     } else {
-        linear.powf(cuw(1.0 / STD_GAMMA)) * cuw(1.055) - cuw(0.055)
+        linear.powf(cuw(1.0 / GAMMA)) * cuw(1.055) - cuw(0.055)
     }
 }
 
 /// Gamma decode an sRGB color channel into the linear space
 pub fn std_gamma_decode<T: Float>(encoded: T) -> T {
-    const SRGB_INV_CUTOFF: f32 = 0.04045;
+    const SRGB_INV_CUTOFF: f64 = 0.04045;
     if encoded <= cuw(SRGB_INV_CUTOFF) {
         encoded / cuw(12.92)
     } else {
-        ((encoded + cuw(0.055)) / cuw(1.055)).powf(cuw(STD_GAMMA))
+        ((encoded + cuw(0.055)) / cuw(1.055)).powf(cuw(GAMMA))
     }
 }
+
+/// The luminance of the maximum PQ signal in nits (candelas per square meter)
+pub const PQ_MAX_NITS: f32 = 10000.0;
+
+/// The luminance of the reference white of HDR video in nits, from ITU-R BT.2408
+pub const REFERENCE_WHITE_NITS: f32 = 203.0;
+
+// the constants of SMPTE ST 2084
+const PQ_M1: f64 = 2610.0 / 16384.0;
+const PQ_M2: f64 = 2523.0 / 4096.0 * 128.0;
+const PQ_C1: f64 = 3424.0 / 4096.0;
+const PQ_C2: f64 = 2413.0 / 4096.0 * 32.0;
+const PQ_C3: f64 = 2392.0 / 4096.0 * 32.0;
+
+/// Encodes a linear channel with the Perceptual Quantizer of SMPTE ST 2084
+///
+/// The linear value is the absolute luminance divided by `PQ_MAX_NITS`, so 1 is 10000 nits.
+/// Negative values are encoded as zero.
+pub fn pq_encode<T: Float>(linear: T) -> T {
+    let y = linear.max(T::zero()).powf(cuw(PQ_M1));
+    ((cuw::<f64, T>(PQ_C1) + cuw::<f64, T>(PQ_C2) * y) / (T::one() + cuw::<f64, T>(PQ_C3) * y))
+        .powf(cuw(PQ_M2))
+}
+
+/// Decodes a channel encoded with the Perceptual Quantizer into the linear value
+///
+/// The linear value is the absolute luminance divided by `PQ_MAX_NITS`.
+pub fn pq_decode<T: Float>(encoded: T) -> T {
+    let e = encoded.max(T::zero()).powf(cuw(1.0 / PQ_M2));
+    ((e - cuw(PQ_C1)).max(T::zero()) / (cuw::<f64, T>(PQ_C2) - cuw::<f64, T>(PQ_C3) * e))
+        .powf(cuw(1.0 / PQ_M1))
+}
+
+/// Encodes an absolute luminance in nits with the Perceptual Quantizer
+pub fn nits_to_pq<T: Float>(nits: T) -> T {
+    pq_encode(nits / cuw(PQ_MAX_NITS))
+}
+
+/// Decodes a Perceptual Quantizer signal into an absolute luminance in nits
+pub fn pq_to_nits<T: Float>(encoded: T) -> T {
+    pq_decode(encoded) * cuw(PQ_MAX_NITS)
+}
+
+// the constants of the HLG curve from ITU-R BT.2100
+const HLG_A: f64 = 0.178_832_77;
+const HLG_B: f64 = 0.284_668_92;
+const HLG_C: f64 = 0.559_910_73;
+
+/// Encodes a linear channel with the Hybrid Log-Gamma OETF of ITU-R BT.2100
+///
+/// The linear value is the relative scene light between 0 and 1.
+pub fn hlg_encode<T: Float>(linear: T) -> T {
+    let linear = linear.max(T::zero());
+    if linear <= cuw(1.0 / 12.0) {
+        (linear * cuw(3.0)).sqrt()
+    } else {
+        cuw::<f64, T>(HLG_A) * (linear * cuw(12.0) - cuw(HLG_B)).ln() + cuw(HLG_C)
+    }
+}
+
+/// Decodes a channel encoded with the Hybrid Log-Gamma OETF into the relative scene light
+pub fn hlg_decode<T: Float>(encoded: T) -> T {
+    let encoded = encoded.max(T::zero());
+    if encoded <= cuw(0.5) {
+        encoded * encoded / cuw(3.0)
+    } else {
+        (((encoded - cuw(HLG_C)) / cuw(HLG_A)).exp() + cuw(HLG_B)) / cuw(12.0)
+    }
+}
+
+/// Returns the system gamma of the HLG OOTF for a display with the given peak luminance
+fn hlg_system_gamma<T: Float>(peak_nits: T) -> T {
+    cuw::<f64, T>(1.2) + cuw::<f64, T>(0.42) * (peak_nits / cuw(1000.0)).log10()
+}
+
+/// Returns the luminance in nits that a display with the given peak luminance shows for a grey
+/// HLG signal
+///
+/// The signal is decoded and the OOTF of ITU-R BT.2100 is applied, with a zero black level.
+pub fn hlg_to_nits<T: Float>(encoded: T, peak_nits: T) -> T {
+    peak_nits * hlg_decode(encoded).powf(hlg_system_gamma(peak_nits))
+}
+
+/// Returns the grey HLG signal that a display with the given peak luminance shows with the
+/// given luminance in nits
+///
+/// This is the inverse of `hlg_to_nits`.
+pub fn nits_to_hlg<T: Float>(nits: T, peak_nits: T) -> T {
+    let relative = (nits / peak_nits).max(T::zero());
+    hlg_encode(relative.powf(hlg_system_gamma(peak_nits).recip()))
+}
+
+/// The sRGB decoding table for 8-bit channels
+///
+/// The table is built on the first call. Loops over many channels should fetch it once and index
+/// it, instead of calling `std_gamma_decode_u8` for every channel.
+pub(crate) fn decode_table_u8() -> &'static [f32; 256] {
+    static TABLE: OnceLock<[f32; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = [0.0; 256];
+        for (i, x) in table.iter_mut().enumerate() {
+            *x = std_gamma_decode((i as u8).conv::<f32>());
+        }
+        table
+    })
+}
+
+/// The sRGB decoding table for 16-bit channels
+///
+/// Like `decode_table_u8`, but for 16-bit channels.
+pub(crate) fn decode_table_u16() -> &'static [f32; 65536] {
+    static TABLE: OnceLock<Box<[f32; 65536]>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let table: Box<[f32]> = (0..=u16::MAX)
+            .map(|i| std_gamma_decode(i.conv::<f32>()))
+            .collect();
+        table.try_into().unwrap()
+    })
+}
+
+/// Gamma decode an 8-bit sRGB color channel into the linear space
+///
+/// Uses a lookup table, and gives the same result as converting the channel to `f32` and
+/// calling `std_gamma_decode`.
+#[inline]
+pub fn std_gamma_decode_u8(encoded: u8) -> f32 {
+    decode_table_u8()[encoded as usize]
+}
+
+/// Gamma decode a 16-bit sRGB color channel into the linear space
+///
+/// Uses a lookup table, and gives the same result as converting the channel to `f32` and
+/// calling `std_gamma_decode`.
+#[inline]
+pub fn std_gamma_decode_u16(encoded: u16) -> f32 {
+    decode_table_u16()[encoded as usize]
+}
+
+/// The tables for the fast 8-bit sRGB encoding
+pub(crate) struct EncodeTables {
+    /// The smallest linear value that is encoded into each value
+    thresholds: [f32; 256],
+    /// The encoded value at the start of each bucket of floats with the same exponent and
+    /// highest mantissa bits
+    buckets: Vec<u8>,
+}
+
+/// The number of low float bits ignored by the encoding buckets
+const BUCKET_SHIFT: u32 = 18;
+
+impl EncodeTables {
+    /// Encodes a linear channel into an 8-bit sRGB channel, see `std_gamma_encode_u8`
+    #[inline]
+    pub(crate) fn encode(&self, linear: f32) -> u8 {
+        if linear.is_nan() || linear <= 0.0 {
+            return 0;
+        }
+        if linear >= 1.0 {
+            return 255;
+        }
+
+        // start from the beginning of the bucket, and step to the right value
+        let mut encoded = self.buckets[(linear.to_bits() >> BUCKET_SHIFT) as usize];
+        while encoded < 255 && linear >= self.thresholds[encoded as usize + 1] {
+            encoded += 1;
+        }
+        encoded
+    }
+}
+
+/// Returns the 8-bit sRGB encoding tables, building them on the first call
+///
+/// Like with the decoding tables, loops should fetch these once.
+pub(crate) fn encode_tables() -> &'static EncodeTables {
+    static TABLES: OnceLock<EncodeTables> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let encode = |linear: f32| std_gamma_encode(linear).conv::<u8>();
+
+        // the positive floats are ordered like their bits, so binary search the bits
+        let mut thresholds = [0.0; 256];
+        for (k, threshold) in thresholds.iter_mut().enumerate().skip(1) {
+            let (mut low, mut high) = (0u32, 1.0f32.to_bits());
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if encode(f32::from_bits(mid)) >= k as u8 {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+            *threshold = f32::from_bits(low);
+        }
+
+        let buckets = (0..=1.0f32.to_bits() >> BUCKET_SHIFT)
+            .map(|bucket| encode(f32::from_bits(bucket << BUCKET_SHIFT)))
+            .collect();
+
+        EncodeTables {
+            thresholds,
+            buckets,
+        }
+    })
+}
+
+/// Gamma encode a linear color channel into an 8-bit sRGB channel
+///
+/// Uses lookup tables, and gives the same result as calling `std_gamma_encode` and converting
+/// the channel to `u8`. The value is clamped between 0 and 1, and NaN is encoded as 0.
+#[inline]
+pub fn std_gamma_encode_u8(linear: f32) -> u8 {
+    encode_tables().encode(linear)
+}
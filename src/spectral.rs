@@ -0,0 +1,289 @@
+mod data;
+
+use crate::*;
+
+/// The CIE standard observers
+///
+/// The `XYZColor`s are defined with the 2° observer. The 10° observer gives slightly different
+/// XYZ values, and it's white point isn't exactly the one of the RGB spaces.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Observer {
+    /// The CIE 1931 2° standard observer
+    Cie1931,
+    /// The CIE 1964 10° supplementary standard observer
+    Cie1964,
+}
+
+/// Returns the index of the table entry before the wavelength and the position between it and
+/// the next entry, or `None` if the wavelength is outside of the tables
+fn table_position(wavelength: f32) -> Option<(usize, f32)> {
+    let pos = (wavelength - data::START) / data::STEP;
+    if !(0.0..=(data::LEN - 1) as f32).contains(&pos) {
+        return None;
+    }
+    let i = (pos as usize).min(data::LEN - 2);
+    Some((i, pos - i as f32))
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+impl Observer {
+    /// Returns the values of the color matching functions at the given wavelength in nanometers
+    ///
+    /// The functions are tabulated between 380 and 780 nm and interpolated linearly. Outside of
+    /// that they are zero.
+    pub fn cmf(self, wavelength: f32) -> (f32, f32, f32) {
+        let table = match self {
+            Observer::Cie1931 => &data::CIE1931,
+            Observer::Cie1964 => &data::CIE1964,
+        };
+        match table_position(wavelength) {
+            Some((i, t)) => {
+                let (a, b) = (table[i], table[i + 1]);
+                (
+                    lerp(a[0], b[0], t),
+                    lerp(a[1], b[1], t),
+                    lerp(a[2], b[2], t),
+                )
+            }
+            None => (0.0, 0.0, 0.0),
+        }
+    }
+}
+
+/// The CIE standard illuminants
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Illuminant {
+    /// Incandescent light, a black body at about 2856 K
+    A,
+    /// Horizon daylight, used in printing
+    D50,
+    /// Noon daylight, the white point of sRGB
+    D65,
+    /// Equal energy
+    E,
+}
+
+impl Illuminant {
+    /// Returns the relative spectral power distribution of this illuminant
+    ///
+    /// The distributions are normalized to 100 at 560 nm.
+    pub fn spectrum(self) -> Spectrum {
+        let table = match self {
+            Illuminant::A => {
+                // illuminant A is defined by this formula instead of a table
+                const C2: f64 = 1.435e7;
+                let a = |wl: f64| (C2 / (2848.0 * wl)).exp_m1();
+                return Spectrum::from_fn(data::START, data::STEP, data::LEN, |wl| {
+                    let wl = wl as f64;
+                    (100.0 * (560.0 / wl).powi(5) * a(560.0) / a(wl)) as f32
+                });
+            }
+            Illuminant::E => return Spectrum::new(data::START, data::STEP, vec![100.0; data::LEN]),
+            Illuminant::D50 => &data::D50,
+            Illuminant::D65 => &data::D65,
+        };
+        Spectrum::new(data::START, data::STEP, table.to_vec())
+    }
+}
+
+/// A spectral power distribution
+///
+/// The distribution is sampled at the given wavelengths in nanometers, which don't need to be
+/// evenly spaced, and it's interpolated linearly between the samples. It's zero outside of the
+/// samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spectrum {
+    samples: Vec<(f32, f32)>,
+}
+
+impl Spectrum {
+    /// Creates an evenly sampled spectrum from the wavelength of the first sample, the distance
+    /// between the samples and the sample values
+    pub fn new(start: f32, step: f32, values: Vec<f32>) -> Self {
+        let samples = values
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| (start + step * i as f32, value))
+            .collect();
+        Spectrum::from_samples(samples)
+    }
+
+    /// Creates a spectrum from pairs of wavelengths and values
+    ///
+    /// The samples are sorted by their wavelength. Samples with a NaN or infinite wavelength are
+    /// ignored.
+    pub fn from_samples(mut samples: Vec<(f32, f32)>) -> Self {
+        samples.retain(|(wl, _)| wl.is_finite());
+        samples.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Spectrum { samples }
+    }
+
+    /// Creates an evenly sampled spectrum from the function of the wavelength
+    pub fn from_fn<F: FnMut(f32) -> f32>(start: f32, step: f32, count: usize, mut f: F) -> Self {
+        Spectrum::new(
+            start,
+            step,
+            (0..count).map(|i| f(start + step * i as f32)).collect(),
+        )
+    }
+
+    /// Returns the relative spectral radiance of a black body at the given temperature in
+    /// kelvins, sampled every 5 nm from 380 to 780 nm
+    ///
+    /// The distribution is normalized to 1 at 560 nm.
+    pub fn blackbody(kelvin: f32) -> Self {
+        let kelvin = kelvin as f64;
+        let reference = planck(560.0, kelvin);
+        Spectrum::from_fn(data::START, data::STEP, data::LEN, |wl| {
+            (planck(wl as f64, kelvin) / reference) as f32
+        })
+    }
+
+    /// Returns the samples of this spectrum as pairs of wavelengths and values
+    pub fn samples(&self) -> &[(f32, f32)] {
+        &self.samples
+    }
+
+    /// Returns the value of this spectrum at the given wavelength in nanometers
+    pub fn value_at(&self, wavelength: f32) -> f32 {
+        // index of the first sample at a longer wavelength
+        let i = self.samples.partition_point(|(wl, _)| *wl <= wavelength);
+        match (self.samples.get(i.wrapping_sub(1)), self.samples.get(i)) {
+            (Some(&(wl_a, a)), Some(&(wl_b, b))) => lerp(a, b, (wavelength - wl_a) / (wl_b - wl_a)),
+            (Some(&(wl, value)), None) if wl == wavelength => value,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns this spectrum sampled evenly at the given wavelengths
+    pub fn resample(&self, start: f32, step: f32, count: usize) -> Self {
+        Spectrum::from_fn(start, step, count, |wl| self.value_at(wl))
+    }
+
+    /// Returns the product of this spectrum with another one, sampled at the samples of both
+    ///
+    /// Can be used to get the light reflected from a surface by multiplying it's reflectance with
+    /// the illuminant.
+    pub fn product(&self, other: &Spectrum) -> Self {
+        let samples = self
+            .samples
+            .iter()
+            .chain(other.samples.iter())
+            .map(|&(wl, _)| (wl, self.value_at(wl) * other.value_at(wl)))
+            .collect();
+        let mut spectrum = Spectrum::from_samples(samples);
+        spectrum.samples.dedup_by(|(a, _), (b, _)| a == b);
+        spectrum
+    }
+
+    /// Integrates this spectrum with the color matching functions of the observer
+    ///
+    /// The integral is evaluated with the trapezoidal rule at the wavelengths of the samples
+    /// and the observer tables, so narrow spectral lines aren't missed.
+    fn tristimulus(&self, observer: Observer) -> (f64, f64, f64) {
+        let (first, last) = (
+            data::START,
+            data::START + data::STEP * (data::LEN - 1) as f32,
+        );
+        let mut wavelengths: Vec<f32> = (0..data::LEN)
+            .map(|i| data::START + data::STEP * i as f32)
+            .chain(self.samples.iter().map(|&(wl, _)| wl))
+            .filter(|wl| (first..=last).contains(wl))
+            .collect();
+        wavelengths.sort_by(|a, b| a.total_cmp(b));
+        wavelengths.dedup();
+
+        let point = |wl: f32| {
+            let value = self.value_at(wl) as f64;
+            let (x, y, z) = observer.cmf(wl);
+            (value * x as f64, value * y as f64, value * z as f64)
+        };
+
+        let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+        for pair in wavelengths.windows(2) {
+            let width = (pair[1] - pair[0]) as f64 / 2.0;
+            let (xa, ya, za) = point(pair[0]);
+            let (xb, yb, zb) = point(pair[1]);
+            x += (xa + xb) * width;
+            y += (ya + yb) * width;
+            z += (za + zb) * width;
+        }
+        (x, y, z)
+    }
+
+    /// Returns the color of a light with this spectral power distribution
+    ///
+    /// The color is scaled to the luminance of 1, so only the relative distribution matters. A
+    /// spectrum without any visible light gives black.
+    pub fn xyz(&self, observer: Observer) -> XYZColor<f32> {
+        let (x, y, z) = self.tristimulus(observer);
+        if y <= 0.0 {
+            return XYZColor::new(0.0, 0.0, 0.0);
+        }
+        XYZColor::new((x / y) as f32, 1.0, (z / y) as f32)
+    }
+
+    /// Returns the color of a surface with this spectral reflectance under the illuminant
+    ///
+    /// The reflectance is between 0 and 1, and the color is scaled so that a perfect reflector
+    /// has the luminance of 1. Note that the white point is the illuminant's, so for the colors
+    /// to match the RGB spaces, the illuminant should be D65.
+    pub fn reflectance_xyz(&self, illuminant: &Spectrum, observer: Observer) -> XYZColor<f32> {
+        let (x, y, z) = self.product(illuminant).tristimulus(observer);
+        let (_, white, _) = illuminant.tristimulus(observer);
+        if white <= 0.0 {
+            return XYZColor::new(0.0, 0.0, 0.0);
+        }
+        XYZColor::new((x / white) as f32, (y / white) as f32, (z / white) as f32)
+    }
+
+    /// Returns the luminance of this spectrum in candelas per square meter, when it's values are
+    /// spectral radiances in watts per steradian per square meter per nanometer
+    pub fn luminance(&self, observer: Observer) -> f32 {
+        const MAX_EFFICACY: f64 = 683.0; // lumens per watt
+        let (_, y, _) = self.tristimulus(observer);
+        (MAX_EFFICACY * y) as f32
+    }
+}
+
+/// Spectral radiance of a black body by Planck's law, without the constant factor
+pub(crate) fn planck(wavelength_nm: f64, kelvin: f64) -> f64 {
+    const C2: f64 = 1.438_776_9e-2; // second radiation constant, m·K
+    let wl = wavelength_nm * 1e-9;
+    1.0 / (wl.powi(5) * (C2 / (wl * kelvin)).exp_m1())
+}
+
+impl RGBColor<f32, LinearSpace> {
+    /// Returns the color of a light with the given spectral power distribution
+    ///
+    /// The color is scaled so that it's brightest channel is 1, and the channels outside of the
+    /// RGB gamut are clamped.
+    pub fn from_spectrum(spectrum: &Spectrum) -> Self {
+        let (r, g, b) = spectrum.xyz(Observer::Cie1931).rgb_unclamped();
+        let max = r.max(g).max(b);
+        if max <= 0.0 {
+            return BaseColor::Black.into();
+        }
+        (r / max, g / max, b / max).into()
+    }
+
+    /// Returns the color of monochromatic light of the given wavelength in nanometers
+    ///
+    /// The pure spectral colors are outside of the RGB gamut, so white is added to the color
+    /// until it fits, and then it's scaled so that it's brightest channel is 1. Wavelengths
+    /// outside of the visible range between 380 and 780 nm give black.
+    pub fn from_wavelength(wavelength: f32) -> Self {
+        let (x, y, z) = Observer::Cie1931.cmf(wavelength);
+        let (r, g, b) = XYZColor::new(x, y, z).rgb_unclamped();
+        let min = r.min(g).min(b).min(0.0);
+        let (r, g, b) = (r - min, g - min, b - min);
+        let max = r.max(g).max(b);
+        if max <= 0.0 {
+            return BaseColor::Black.into();
+        }
+        (r / max, g / max, b / max).into()
+    }
+}
@@ -0,0 +1,150 @@
+//! Statistics over sets of colors
+//!
+//! Hues are angles, so they can't be averaged like other values: the mean of 350° and 10° is
+//! 0°, not 180°. The circular functions treat the angles as unit vectors on the color wheel and
+//! average those instead.
+//!
+//! The circular mean of angles is `mean_angle`, or `weighted_mean_angle` with weights.
+//!
+//! All the weighted functions ignore values with a zero, negative or NaN weight.
+
+use crate::angle::Resultant;
+use crate::*;
+
+/// Returns the weighted circular variance of the angles
+///
+/// The variance is between 0 and 1. It's 0 if all the angles are the same, and 1 if they are
+/// spread evenly around the circle. Returns `None` if there are no angles with a positive
+/// weight.
+pub fn circular_variance<A: Angle, I: IntoIterator<Item = (A, f32)>>(angles: I) -> Option<f32> {
+    let sum = Resultant::new(angles);
+    if sum.weight() <= 0.0 {
+        return None;
+    }
+    Some((1.0 - sum.length()).clamp(0.0, 1.0) as f32)
+}
+
+/// Returns the weighted histogram of the angles
+///
+/// The full circle is divided into `bins` bins of equal size, the first one starting from zero.
+/// The histogram holds the sum of the weights of the angles in every bin, normalized so that
+/// they sum up to 1, or all zeros if there are no angles with a positive weight.
+pub fn hue_histogram<A: Angle, I: IntoIterator<Item = (A, f32)>>(
+    angles: I,
+    bins: usize,
+) -> Vec<f32> {
+    if bins == 0 {
+        return Vec::new();
+    }
+    let mut histogram = vec![0.0f64; bins];
+    for (angle, weight) in angles {
+        if weight.is_nan() || weight <= 0.0 {
+            continue;
+        }
+        let bin = ((angle.turns() * bins as f64) as usize).min(bins - 1);
+        histogram[bin] += weight as f64;
+    }
+
+    let total: f64 = histogram.iter().sum();
+    histogram
+        .into_iter()
+        .map(|x| if total > 0.0 { (x / total) as f32 } else { 0.0 })
+        .collect()
+}
+
+/// Returns the hues of the colors weighted by their chroma
+///
+/// Greys don't have a meaningful hue, so the weight of a color is it's saturation times it's
+/// value. This makes the greys count nothing and the vivid colors count the most.
+fn chroma_weighted<H, T, S, I>(colors: I) -> impl Iterator<Item = (H, f32)>
+where
+    H: Angle,
+    T: Channel,
+    I: IntoIterator<Item = HSVColor<H, T, S>>,
+{
+    colors.into_iter().map(|color| {
+        let (h, s, v) = color.tuple();
+        (h, s.conv::<f32>() * v.conv::<f32>())
+    })
+}
+
+/// Returns the mean hue of the colors
+///
+/// The hues are weighted by the chroma of the colors, so greys don't affect the result. Returns
+/// `None` if all the colors are grey or their hues cancel each other out.
+pub fn mean_hue<H, T, S, I>(colors: I) -> Option<H>
+where
+    H: Angle,
+    T: Channel,
+    I: IntoIterator<Item = HSVColor<H, T, S>>,
+{
+    weighted_mean_angle(chroma_weighted(colors))
+}
+
+/// Returns the circular variance of the hues of the colors
+///
+/// The hues are weighted like in `mean_hue`. Returns `None` if all the colors are grey.
+pub fn hue_variance<H, T, S, I>(colors: I) -> Option<f32>
+where
+    H: Angle,
+    T: Channel,
+    I: IntoIterator<Item = HSVColor<H, T, S>>,
+{
+    circular_variance(chroma_weighted(colors))
+}
+
+/// Returns the histogram of the hues of the colors with the given number of bins
+///
+/// The hues are weighted like in `mean_hue`, see `hue_histogram` for the bins.
+pub fn color_hue_histogram<H, T, S, I>(colors: I, bins: usize) -> Vec<f32>
+where
+    H: Angle,
+    T: Channel,
+    I: IntoIterator<Item = HSVColor<H, T, S>>,
+{
+    hue_histogram(chroma_weighted(colors), bins)
+}
+
+/// The space where colors are averaged
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum MeanSpace {
+    /// Linear RGB, which gives the color of the mixed light
+    Linear,
+    /// Oklab, which gives a color that looks like the average of the colors
+    Oklab,
+}
+
+/// Returns the mean of the colors averaged in the given space
+///
+/// Returns `None` if there are no colors.
+pub fn mean_color<C, I>(colors: I, space: MeanSpace) -> Option<C>
+where
+    C: ToLinear + FromLinear,
+    I: IntoIterator<Item = C>,
+{
+    let (mut sum, mut count) = ([0.0f64; 3], 0usize);
+    for color in colors {
+        let linear = color.to_linear();
+        let channels = match space {
+            MeanSpace::Linear => linear.array(),
+            MeanSpace::Oklab => {
+                let lab = linear.oklab();
+                [lab.l, lab.a, lab.b]
+            }
+        };
+        for (sum, x) in sum.iter_mut().zip(channels.iter()) {
+            *sum += *x as f64;
+        }
+        count += 1;
+    }
+
+    if count == 0 {
+        return None;
+    }
+    let [x, y, z] = sum.map(|x| (x / count as f64) as f32);
+    let linear = match space {
+        MeanSpace::Linear => (x, y, z).into(),
+        MeanSpace::Oklab => OklabColor::new(x, y, z).rgb(),
+    };
+    Some(C::from_linear(linear))
+}
@@ -0,0 +1,125 @@
+use crate::*;
+
+/// The lowest color temperature supported, in kelvins
+pub const MIN_TEMPERATURE: f32 = 1000.0;
+/// The highest color temperature supported, in kelvins
+pub const MAX_TEMPERATURE: f32 = 40_000.0;
+
+/// Returns the color of a black body radiator at the given temperature in kelvins
+///
+/// The color is computed by integrating Planck's law with the CIE 1931 2° observer, and it's
+/// scaled to the luminance of 1. The temperature is clamped between `MIN_TEMPERATURE` and
+/// `MAX_TEMPERATURE`, and a NaN temperature is treated as `MIN_TEMPERATURE`.
+pub fn blackbody(kelvin: f32) -> XYZColor<f32> {
+    let kelvin = if kelvin.is_nan() {
+        MIN_TEMPERATURE
+    } else {
+        f32::clamp(kelvin, MIN_TEMPERATURE, MAX_TEMPERATURE)
+    };
+    Spectrum::blackbody(kelvin).xyz(Observer::Cie1931)
+}
+
+/// Returns the color of CIE daylight with the given correlated color temperature in kelvins
+///
+/// The color is on the CIE daylight locus and has the luminance of 1. The daylight locus is
+/// only defined between 4000 and 25000 kelvins, and `None` is returned outside of it.
+pub fn daylight(kelvin: f32) -> Option<XYZColor<f32>> {
+    if !(4000.0..=25_000.0).contains(&kelvin) {
+        return None;
+    }
+    let t = kelvin as f64;
+
+    let x = if t <= 7000.0 {
+        -4.6070e9 / t.powi(3) + 2.9678e6 / t.powi(2) + 0.09911e3 / t + 0.244_063
+    } else {
+        -2.0064e9 / t.powi(3) + 1.9018e6 / t.powi(2) + 0.24748e3 / t + 0.237_040
+    };
+    let y = -3.000 * x * x + 2.870 * x - 0.275;
+
+    Some(XYZColor::from_chromaticity(x as f32, y as f32, 1.0))
+}
+
+impl RGBColor<f32, LinearSpace> {
+    /// Returns the color of a black body radiator at the given temperature in kelvins
+    ///
+    /// The color is scaled so that it's brightest channel is 1, and the channels outside of
+    /// the RGB gamut are clamped. The temperature is clamped to the supported range like in
+    /// `blackbody`, so any input gives a valid color.
+    pub fn from_temperature(kelvin: f32) -> Self {
+        let (r, g, b) = blackbody(kelvin).rgb_unclamped();
+        let max = r.max(g).max(b);
+        (r / max, g / max, b / max).into()
+    }
+}
+
+/// Returns the CIE 1960 UCS chromaticity coordinates of the color
+fn uv(color: XYZColor<f32>) -> (f32, f32) {
+    let (x, y) = color.chromaticity();
+    let d = -2.0 * x + 12.0 * y + 3.0;
+    (4.0 * x / d, 6.0 * y / d)
+}
+
+/// Estimates the correlated color temperature of the color with McCamy's approximation
+///
+/// The approximation is fast, but only accurate for colors near the Planckian locus between
+/// roughly 2000 and 12500 kelvins.
+pub fn cct_mccamy<C: ToLinear>(color: &C) -> f32 {
+    let (x, y) = color.to_linear().xyz().chromaticity();
+    let n = (x - 0.3320) / (0.1858 - y);
+    449.0 * n.powi(3) + 3525.0 * n.powi(2) + 6823.3 * n + 5520.33
+}
+
+/// Estimates the correlated color temperature of the color in kelvins
+///
+/// Finds the temperature of the black body whose chromaticity is closest to the color's in the
+/// CIE 1960 UCS, like the method of Robertson (1968) but searching the locus numerically. The
+/// result is between `MIN_TEMPERATURE` and `MAX_TEMPERATURE`.
+///
+/// The color is converted through linear RGB, so colors outside of the RGB gamut, like black
+/// bodies below about 2500 kelvins, are clamped first. Use `XYZColor::cct` for those.
+pub fn correlated_color_temperature<C: ToLinear>(color: &C) -> f32 {
+    color.to_linear().xyz().cct()
+}
+
+impl XYZColor<f32> {
+    /// Estimates the correlated color temperature of this color in kelvins
+    ///
+    /// See `correlated_color_temperature` for details. Returns NaN if the chromaticity of the
+    /// color isn't finite, which happens when a channel is infinite or NaN.
+    pub fn cct(self) -> f32 {
+        let (u, v) = uv(self);
+        if !u.is_finite() || !v.is_finite() {
+            return f32::NAN;
+        }
+
+        // the search is done in micro reciprocal degrees, which are more uniform than kelvins
+        let dist = |mired: f32| {
+            let (lu, lv) = uv(blackbody(1e6 / mired));
+            (lu - u).hypot(lv - v)
+        };
+        let (min, max) = (1e6 / MAX_TEMPERATURE, 1e6 / MIN_TEMPERATURE);
+
+        // find the neighbourhood of the closest point with a coarse scan
+        const STEPS: usize = 64;
+        let step = (max - min) / STEPS as f32;
+        let best = (0..=STEPS)
+            .map(|i| min + step * i as f32)
+            .min_by(|a, b| dist(*a).total_cmp(&dist(*b)))
+            .unwrap();
+
+        // and refine it with a golden section search
+        const INV_PHI: f32 = 0.618_034;
+        let (mut low, mut high) = ((best - step).max(min), (best + step).min(max));
+        for _ in 0..32 {
+            let a = high - (high - low) * INV_PHI;
+            let b = low + (high - low) * INV_PHI;
+            if dist(a) < dist(b) {
+                high = b;
+            } else {
+                low = a;
+            }
+        }
+
+        1e6 / ((low + high) / 2.0)
+    }
+}
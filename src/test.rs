@@ -105,8 +105,7 @@ fn angle_conversion() {
 
 #[test]
 fn normalization() {
-    use std::f32::{INFINITY, NEG_INFINITY};
-    let rgba = SRGBAColor::new((2.0, -10.0, NEG_INFINITY), INFINITY);
+    let rgba = SRGBAColor::new((2.0, -10.0, f32::NEG_INFINITY), f32::INFINITY);
     let hsv1 = StdHSVColor::new(-90.0, 2.0, -5.0);
     let hsv2 = StdHSVColor::new(400.0, 2.0, -5.0);
     let hsv3 = StdHSVColor::new(60.0, 0.0, 0.5);
@@ -124,3 +123,1195 @@ fn normalization() {
     assert_eq!(hsv6.array::<f32>(), [0.0, 0.0, 0.8]);
     assert_eq!(hsv7.tuple(), (0.0.into(), 0.0, 0.0));
 }
+
+#[test]
+fn linear_arithmetic() {
+    let mut acc = LinRGBAColor::new((0.0, 0.0, 0.0), 0.0);
+    for _ in 0..4 {
+        acc += LinRGBAColor::new((0.125, 0.25, 0.0625), 0.25);
+    }
+    assert_eq!(acc.tuple(), (0.5, 1.0, 0.25, 1.0));
+
+    acc -= LinRGBAColor::new((0.25, 0.5, 0.0), 0.5);
+    acc *= 0.5;
+    assert_eq!(acc.tuple(), (0.125, 0.25, 0.125, 0.25));
+
+    let mut rgb = LinRGBColor::new(0.5, 0.25, 1.0);
+    rgb /= LinRGBColor::new(1.0, 0.5, 1.0);
+    assert_eq!(rgb.tuple(), (0.5, 0.5, 1.0));
+    assert_eq!((-rgb).tuple(), (-0.5, -0.5, -1.0));
+    assert!(!(-rgb).is_normal());
+    let darker = rgb + -LinRGBColor::new(0.25, 0.75, 0.5);
+    assert_eq!(darker.tuple(), (0.25, 0.0, 0.5));
+
+    let a = LinRGB48Color::new(60_000, 100, 5);
+    let b = LinRGB48Color::new(10_000, 200, 10);
+    assert_eq!(a.saturating_add(b).tuple(), (65_535, 300, 15));
+    assert_eq!(a.saturating_sub(b).tuple(), (50_000, 0, 0));
+    assert_eq!(a.saturating_mul(b).tuple(), (65_535, 20_000, 50));
+}
+
+#[test]
+fn hsv_arithmetic() {
+    let mut hsv = StdHSVColor::new(300.0, 0.5, 0.5);
+    hsv += StdHSVColor::new(90.0, 0.25, 0.75);
+    assert_eq!(hsv.tuple(), (Deg(30.0), 0.75, 1.0));
+
+    hsv -= StdHSVColor::new(60.0, 0.5, 0.5);
+    assert_eq!(hsv.tuple(), (Deg(330.0), 0.25, 0.5));
+}
+
+#[test]
+fn color_adjustments() {
+    let red = SRGB24Color::new(255, 0, 0);
+
+    assert_eq!(red.lighten(1.0), SRGB24Color::new(255, 255, 255));
+    assert_eq!(red.lighten(0.0), red);
+    assert_eq!(red.darken(1.0), SRGB24Color::new(0, 0, 0));
+    assert_eq!(red.lighten(0.5).tuple(), (255, 188, 188));
+    assert_eq!(red.desaturate(0.5).tuple(), (255, 128, 128));
+    assert_eq!(red.desaturate(0.5).saturate(1.0), red);
+    assert_eq!(red.shift_hue(Deg(120.0)), SRGB24Color::new(0, 255, 0));
+    assert_eq!(red.shift_hue(Deg(-120.0)), SRGB24Color::new(0, 0, 255));
+    assert_eq!(red.invert(), SRGB24Color::new(0, 255, 255));
+    assert_eq!(red.grayscale(), SRGB24Color::new(127, 127, 127));
+
+    let hsv = StdHSVColor::new(0.0, 1.0, 1.0);
+    assert_eq!(
+        hsv.shift_hue(Rad(std::f32::consts::PI)).tuple(),
+        (Deg(180.0), 1.0, 1.0)
+    );
+    assert_eq!(hsv.desaturate(0.25).tuple(), (Deg(0.0), 0.75, 1.0));
+
+    let rgba = red.with_alpha(128u8);
+    assert_eq!(rgba.invert().tuple(), (0, 255, 255, 128));
+    assert_eq!(rgba.with_alpha(0.5).tuple(), (255, 0, 0, 0.5));
+}
+
+#[test]
+fn oklab_conversion() {
+    let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+
+    let white = LinRGBColor::new(1.0, 1.0, 1.0).oklab();
+    assert!(close(white.l, 1.0) && close(white.a, 0.0) && close(white.b, 0.0));
+
+    let (l, a, b) = SRGBColor::new(1.0, 0.0, 0.0).std_decode().oklab().tuple();
+    assert!(close(l, 0.628) && close(a, 0.225) && close(b, 0.126));
+
+    let srgb = SRGB24Color::new(128, 255, 55);
+    let lch = srgb.conv::<f32>().std_decode().oklch::<Deg<f32>>();
+    assert_eq!(srgb, lch.rgb().std_encode().conv());
+    assert!(lch.c <= lch.max_chroma() + 1e-4);
+}
+
+#[test]
+fn color_harmonies() {
+    use crate::harmony::*;
+
+    let red = SRGB24Color::new(255, 0, 0);
+    let hex = |colors: &[SRGB24Color]| -> Vec<String> {
+        colors.iter().map(|c| format!("{:X}", c)).collect()
+    };
+
+    assert_eq!(hex(&complementary(red)), ["FF0000", "00FFFF"]);
+    assert_eq!(hex(&triadic(red)), ["FF0000", "00FF00", "0000FF"]);
+    assert_eq!(
+        hex(&analogous(red, Deg(60.0))),
+        ["FF0000", "FF00FF", "FFFF00"]
+    );
+    assert_eq!(
+        hex(&split_complementary(red, Deg(60.0))),
+        ["FF0000", "00FF00", "0000FF"]
+    );
+    assert_eq!(hex(&square(red)), ["FF0000", "80FF00", "00FFFF", "8000FF"]);
+    assert_eq!(hex(&monochromatic(red, 1)), ["BC0000", "FF0000", "FFBCBC"]);
+
+    let lch = red.conv::<f32>().std_decode().oklch::<Deg<f32>>();
+    let [_, comp] = complementary(lch);
+    assert!((comp.h.0 - (lch.h.0 + 180.0) % 360.0).abs() < 1e-3);
+    assert_eq!(comp.l, lch.l);
+}
+
+#[test]
+fn contrast() {
+    let white = SRGB24Color::from(BaseColor::White);
+    let black = SRGB24Color::from(BaseColor::Black);
+    let grey = SRGB24Color::new(0x88, 0x88, 0x88);
+
+    assert!((contrast_ratio(&white, &black) - 21.0).abs() < 1e-3);
+    assert!((contrast_ratio(&grey, &white) - 3.54).abs() < 1e-2);
+    assert_eq!(contrast_ratio(&grey, &white), contrast_ratio(&white, &grey));
+
+    assert!(passes_wcag(&grey, &white, WcagLevel::AA, TextSize::Large));
+    assert!(!passes_wcag(&grey, &white, WcagLevel::AA, TextSize::Normal));
+    assert!(passes_wcag(&black, &grey, WcagLevel::AA, TextSize::Normal));
+    assert!(!passes_wcag(
+        &black,
+        &grey,
+        WcagLevel::AAA,
+        TextSize::Normal
+    ));
+
+    assert!((apca_contrast(&black, &white) - 106.04).abs() < 0.01);
+    assert!((apca_contrast(&white, &black) + 107.88).abs() < 0.01);
+    assert_eq!(apca_contrast(&grey, &grey), 0.0);
+
+    let bg = StdHSVColor::new(60.0, 1.0, 1.0);
+    assert_eq!(best_text_color(&bg, vec![white, black]), Some(black));
+    assert_eq!(best_text_color(&bg, Vec::<SRGB24Color>::new()), None);
+
+    assert_eq!(
+        ansi_bgcolor(SRGB24Color::new(0, 0, 128), "x"),
+        "\u{1B}[38;2;255;255;255m\u{1B}[48;2;0;0;128mx\u{1B}[0m"
+    );
+}
+
+#[test]
+fn contrasting_color() {
+    let white = SRGB24Color::from(BaseColor::White);
+    let brand = SRGB24Color::new(0xff, 0x99, 0x33);
+
+    let target = ContrastTarget::Wcag(4.5);
+    assert!(!target.is_met(&brand, &white));
+
+    let fixed = find_contrasting_color(&brand, &white, target).unwrap();
+    assert!(target.is_met(&fixed, &white));
+    assert!(contrast_ratio(&fixed, &white) < 4.6);
+
+    // the hue should stay the same
+    let hue = |c: &SRGB24Color| c.to_linear().oklch::<Deg<f32>>().h.0;
+    assert!((hue(&fixed) - hue(&brand)).abs() < 2.0);
+
+    let apca = ContrastTarget::Apca(60.0);
+    let fixed = find_contrasting_color(&brand, &white, apca).unwrap();
+    assert!(apca_contrast(&fixed, &white) >= 60.0);
+
+    let grey = SRGB24Color::new(0x77, 0x77, 0x77);
+    let err = find_contrasting_color(&white, &grey, ContrastTarget::Wcag(7.0)).unwrap_err();
+    assert!(err.best_contrast < 7.0);
+    assert_eq!(
+        find_contrasting_color(&white, &grey, ContrastTarget::Wcag(4.0)),
+        Ok(white)
+    );
+}
+
+#[test]
+fn color_vision_deficiency() {
+    let red = LinRGBColor::new(1.0, 0.0, 0.0);
+    let grey = LinRGBColor::new(0.5, 0.5, 0.5);
+
+    for &d in &[Deficiency::Protan, Deficiency::Deutan, Deficiency::Tritan] {
+        // greys are seen the same by everyone
+        let sim = grey.simulate_cvd(d, 1.0);
+        assert!(sim.into_iter().all(|c| (c - 0.5).abs() < 1e-4));
+        assert_eq!(red.simulate_cvd(d, 0.0), red);
+    }
+
+    let dist = |a: LinRGBColor, b: LinRGBColor| a.oklab().distance(&b.oklab());
+    // a red and a green with the same luminance
+    let (red, green) = (
+        LinRGBColor::new(0.6, 0.1, 0.1),
+        LinRGBColor::new(0.1, 0.25, 0.1),
+    );
+    let protan = |c: LinRGBColor| c.simulate_cvd(Deficiency::Protan, 1.0);
+    let mild = |c: LinRGBColor| c.simulate_cvd(Deficiency::Protan, 0.5);
+
+    // red and green get harder to tell apart, more so with a stronger deficiency
+    assert!(dist(protan(red), protan(green)) < dist(mild(red), mild(green)));
+    assert!(dist(mild(red), mild(green)) < dist(red, green));
+
+    let achromat = LinRGBColor::new(1.0, 0.0, 0.0).simulate_cvd(Deficiency::Achromat, 1.0);
+    assert_eq!(achromat.tuple(), (0.2126, 0.2126, 0.2126));
+
+    let orange = LinRGBColor::new(1.0, 0.3, 0.0);
+    let fixed = orange.daltonize(Deficiency::Protan, 1.0);
+    assert!(dist(protan(fixed), protan(green)) > dist(protan(orange), protan(green)));
+    assert_eq!(orange.daltonize(Deficiency::Achromat, 1.0), orange);
+}
+
+#[test]
+fn classification() {
+    use self::BaseColor::*;
+
+    let top = |hex: &str| shades(SRGB24Color::from_hex(hex).unwrap().conv())[0].0;
+
+    assert_eq!(top("000000"), Black);
+    assert_eq!(top("808080"), Grey);
+    assert_eq!(top("f4f4f4"), White);
+    assert_eq!(top("d92923"), Red);
+    assert_eq!(top("ec8b23"), Orange);
+    assert_eq!(top("964B00"), Brown);
+    assert_eq!(top("eee313"), Yellow);
+    assert_eq!(top("38cc2d"), Green);
+    assert_eq!(top("1ed4e5"), Cyan);
+    assert_eq!(top("282bd4"), Blue);
+    assert_eq!(top("7a0cc6"), Purple);
+    assert_eq!(top("f116c8"), Magenta);
+    assert_eq!(top("ff95bf"), Pink);
+
+    // colors that fell between the default categories
+    let classifier = Classifier::default();
+    let dark_red = OklchColor::new(0.45, 0.15, Deg(1.0f32));
+    assert_eq!(classifier.classify(&dark_red)[0].0, Red);
+    let olive = OklchColor::new(0.65, 0.1, Deg(90.0f32));
+    assert_eq!(classifier.classify(&olive)[0].0, Yellow);
+    for i in 0..1000 {
+        let color = OklchColor::new(0.25 + (i % 10) as f32 * 0.08, 0.1, Deg(i as f32 * 0.36));
+        assert!(!classifier.classify(&color).is_empty());
+    }
+
+    let mixed = shades(SRGB24Color::from_hex("9999bf").unwrap().conv());
+    assert_eq!(mixed.len(), 3);
+    assert!((mixed.iter().map(|(_, a)| a).sum::<f32>() - 1.0).abs() < 1e-6);
+
+    let custom: Classifier<String> = "
+        # label  hue      lightness  chroma
+        dark     -        ..0.5      ..
+        teal     210+-20  0.3..0.7   0.04..
+    "
+    .parse()
+    .unwrap();
+
+    let teal = SRGB24Color::new(0x22, 0x7d, 0x8f);
+    assert_eq!(custom.classify(&teal), vec![("teal".to_string(), 1.0)]);
+    assert_eq!(custom.categories()[0].chroma, 0.0..=f32::INFINITY);
+    let light_red = SRGB24Color::new(0xf0, 0x80, 0x80);
+    assert_eq!(custom.classify(&light_red), vec![("dark".to_string(), 1.0)]);
+
+    let err = "ok - 0..1 0..\nbad 10+- 0..1 0..".parse::<Classifier<String>>();
+    assert_eq!(
+        err.unwrap_err(),
+        ParseClassifierError {
+            line: 2,
+            field: "hue"
+        }
+    );
+}
+
+#[test]
+fn base_colors() {
+    assert_eq!(BaseColor::all().count(), 13);
+    for base in BaseColor::all() {
+        assert_eq!(base.to_string().parse(), Ok(base));
+
+        let hsv = StdHSVColor::from(base);
+        assert_eq!(base.hue().unwrap_or_default(), hsv.h);
+        let rgb = SRGB24Color::from(base);
+        assert_eq!(
+            hsv.rgb().conv::<u8>().hsv::<Deg<f32>>().h.0.round(),
+            hsv.h.0
+        );
+        assert!((base.lightness() - OklabColor::<f32>::from(base).l).abs() < 1e-3);
+
+        let rgba = SRGBA32Color::from(base);
+        assert_eq!(rgba.tuple(), (rgb.r, rgb.g, rgb.b, 255));
+    }
+
+    assert_eq!(" Gray ".parse(), Ok(BaseColor::Grey));
+    assert_eq!("violet".parse(), Ok(BaseColor::Purple));
+    assert_eq!("teal".parse::<BaseColor>(), Err(ParseBaseColorError));
+
+    assert!((BaseColor::White.lightness() - 1.0).abs() < 1e-4);
+    assert!(BaseColor::Black.lightness().abs() < 1e-4);
+    assert!(BaseColor::Brown.lightness() < BaseColor::Orange.lightness());
+    assert_eq!(BaseColor::Grey.hue(), None);
+}
+
+#[test]
+fn color_temperature() {
+    let d65 = SRGBColor::from(BaseColor::White);
+    let (x, y) = d65.to_linear().xyz().chromaticity();
+    assert!((x - 0.3127).abs() < 1e-4 && (y - 0.3290).abs() < 1e-4);
+
+    // the chromaticities of the Planckian locus from CIE 15:2004
+    let (x, y) = blackbody(2856.0).chromaticity();
+    assert!((x - 0.4476).abs() < 2e-3 && (y - 0.4074).abs() < 2e-3);
+    let (x, y) = daylight(6504.0).unwrap().chromaticity();
+    assert!((x - 0.3127).abs() < 1e-3 && (y - 0.3291).abs() < 1e-3);
+    assert_eq!(daylight(3000.0), None);
+
+    let warm = LinRGBColor::from_temperature(2700.0);
+    assert_eq!(warm.r, 1.0);
+    assert!(warm.g < 0.7 && warm.b < warm.g);
+    let cold = LinRGBColor::from_temperature(20_000.0);
+    assert_eq!(cold.b, 1.0);
+    for &kelvin in &[f32::NAN, 0.0, -500.0, f32::INFINITY] {
+        assert!(LinRGBColor::from_temperature(kelvin).is_normal());
+    }
+    assert_eq!(
+        LinRGBColor::from_temperature(f32::NAN),
+        LinRGBColor::from_temperature(MIN_TEMPERATURE)
+    );
+
+    for &kelvin in &[1500.0, 2700.0, 4000.0, 6500.0, 10_000.0, 30_000.0] {
+        let cct = blackbody(kelvin).cct();
+        assert!(
+            (cct - kelvin).abs() / kelvin < 0.01,
+            "{} != {}",
+            cct,
+            kelvin
+        );
+    }
+
+    assert!(XYZColor::new(f32::INFINITY, 1.0, 1.0).cct().is_nan());
+    let nan = XYZColor {
+        x: f32::NAN,
+        y: 1.0,
+        z: 1.0,
+    };
+    assert!(nan.cct().is_nan());
+
+    assert!((correlated_color_temperature(&d65) - 6504.0).abs() < 100.0);
+    assert!((cct_mccamy(&d65) - 6504.0).abs() < 100.0);
+}
+
+#[test]
+fn spectral() {
+    let near = |(x, y): (f32, f32), (ex, ey): (f32, f32)| {
+        assert!(
+            (x - ex).abs() < 1e-3 && (y - ey).abs() < 1e-3,
+            "{:?}",
+            (x, y)
+        );
+    };
+
+    // the white points of the standard illuminants
+    near(
+        Illuminant::D65
+            .spectrum()
+            .xyz(Observer::Cie1931)
+            .chromaticity(),
+        (0.3127, 0.3290),
+    );
+    near(
+        Illuminant::D50
+            .spectrum()
+            .xyz(Observer::Cie1931)
+            .chromaticity(),
+        (0.3457, 0.3585),
+    );
+    near(
+        Illuminant::A
+            .spectrum()
+            .xyz(Observer::Cie1931)
+            .chromaticity(),
+        (0.4476, 0.4074),
+    );
+    near(
+        Illuminant::E
+            .spectrum()
+            .xyz(Observer::Cie1931)
+            .chromaticity(),
+        (1.0 / 3.0, 1.0 / 3.0),
+    );
+    near(
+        Illuminant::D65
+            .spectrum()
+            .xyz(Observer::Cie1964)
+            .chromaticity(),
+        (0.3138, 0.3310),
+    );
+
+    let white = LinRGBColor::from_spectrum(&Illuminant::D65.spectrum());
+    assert!(white.r > 0.99 && white.g > 0.99 && white.b > 0.99);
+
+    // irregular samples and interpolation
+    let spectrum = Spectrum::from_samples(vec![(600.0, 2.0), (500.0, 1.0), (700.0, 0.0)]);
+    assert_eq!(spectrum.samples()[0], (500.0, 1.0));
+    assert_eq!(spectrum.value_at(550.0), 1.5);
+    assert_eq!(spectrum.value_at(700.0), 0.0);
+    assert_eq!(spectrum.value_at(400.0), 0.0);
+    let noisy = vec![
+        (f32::NAN, 5.0),
+        (600.0, 2.0),
+        (f32::INFINITY, 1.0),
+        (500.0, 1.0),
+    ];
+    let noisy = Spectrum::from_samples(noisy);
+    assert_eq!(noisy.samples(), &[(500.0, 1.0), (600.0, 2.0)]);
+    assert!(noisy.xyz(Observer::Cie1931).y.is_finite());
+    let resampled = spectrum.resample(500.0, 50.0, 5);
+    assert_eq!(resampled.samples()[1], (550.0, 1.5));
+
+    // a perfect reflector is the white of the illuminant, and an ideal red filter is red
+    let d65 = Illuminant::D65.spectrum();
+    let reflector = Spectrum::new(380.0, 400.0, vec![1.0, 1.0]);
+    let xyz = reflector.reflectance_xyz(&d65, Observer::Cie1931);
+    near(xyz.chromaticity(), (0.3127, 0.3290));
+    assert!((xyz.y - 1.0).abs() < 1e-6);
+    let red_filter = Spectrum::from_samples(vec![(599.0, 0.0), (600.0, 1.0), (780.0, 1.0)]);
+    let red = red_filter.reflectance_xyz(&d65, Observer::Cie1931).rgb();
+    assert!(red.r > 0.3 && red.g < 0.1 && red.b < 0.01);
+
+    // a watt per steradian per square meter of 555 nm light is 683 nits
+    let line = Spectrum::from_samples(vec![(554.0, 0.0), (555.0, 1.0), (556.0, 0.0)]);
+    assert!((line.luminance(Observer::Cie1931) - 683.0).abs() < 1.0);
+
+    let red = LinRGBColor::from_wavelength(650.0);
+    assert_eq!(red.r, 1.0);
+    assert!(red.g < 0.1 && red.b < 0.1);
+    let green = LinRGBColor::from_wavelength(530.0);
+    assert_eq!(green.g, 1.0);
+    let blue = LinRGBColor::from_wavelength(460.0);
+    assert_eq!(blue.b, 1.0);
+    assert_eq!(LinRGBColor::from_wavelength(300.0), BaseColor::Black.into());
+}
+
+#[test]
+fn quantization() {
+    use crate::quantize::*;
+
+    // an image of 60% red, 30% blue and 10% of two similar greens
+    let red = SRGB24Color::from((200, 20, 20));
+    let blue = SRGB24Color::from((20, 40, 220));
+    let greens = [SRGB24Color::from((30, 180, 40)), (34, 186, 44).into()];
+    let mut pixels = vec![red; 60];
+    pixels.extend(vec![blue; 30]);
+    pixels.extend(greens.iter().cycle().take(10));
+
+    let check = |palette: Vec<(SRGB24Color, f32)>| {
+        assert_eq!(palette.len(), 3, "{:?}", palette);
+        assert_eq!(palette[0].0, red);
+        assert!((palette[0].1 - 0.6).abs() < 1e-6);
+        assert_eq!(palette[1].0, blue);
+        assert!((palette[1].1 - 0.3).abs() < 1e-6);
+        let (green, weight) = palette[2];
+        assert!((weight - 0.1).abs() < 1e-6);
+        assert!(green.g > 175 && green.g < 190);
+    };
+    check(median_cut(&pixels, 3));
+    check(octree(&pixels, 3));
+    check(kmeans(&pixels, 3, 16, 1));
+
+    // the same seed gives the same palette
+    let gradient: Vec<SRGB24Color> = (0..=255u8).map(|x| (x, 255 - x, x / 2).into()).collect();
+    let a = kmeans(&gradient, 5, 16, 42);
+    assert_eq!(a.len(), 5);
+    assert_eq!(a, kmeans(&gradient, 5, 16, 42));
+    assert!(octree(&gradient, 5).len() <= 5);
+    assert_eq!(median_cut(&gradient, 5).len(), 5);
+    let sum: f32 = a.iter().map(|(_, w)| w).sum();
+    assert!((sum - 1.0).abs() < 1e-5);
+
+    // transparent pixels don't count, and there can't be more colors than in the image
+    let pixels = [
+        SRGBA32Color::new(red, 255),
+        SRGBA32Color::new(blue, 0),
+        SRGBA32Color::new(red, 128),
+    ];
+    assert_eq!(median_cut(&pixels, 4), vec![(red, 1.0)]);
+    assert_eq!(octree(&pixels, 4), vec![(red, 1.0)]);
+    assert_eq!(kmeans(&pixels, 4, 16, 0), vec![(red, 1.0)]);
+    assert!(median_cut::<SRGB24Color>(&[], 4).is_empty());
+}
+
+#[test]
+fn dithering() {
+    use crate::dither::*;
+
+    let palette = [BaseColor::Black.into(), BaseColor::White.into()];
+    let grey = LinRGBColor::from((0.25, 0.25, 0.25));
+    let image = vec![grey; 32 * 32];
+
+    // the dithered image has the same average linear lightness as the original
+    let mean = |method| {
+        let indices = dither(&image, 32, &palette, method, &LinearDistance);
+        assert_eq!(indices.len(), image.len());
+        indices.iter().sum::<usize>() as f32 / indices.len() as f32
+    };
+    // linear 25% grey is nearer to black in linear RGB, but to white in Oklab
+    assert_eq!(mean(Dither::None), 0.0);
+    assert_eq!(
+        dither(&image, 32, &palette, Dither::None, &OklabDistance),
+        vec![1; 32 * 32]
+    );
+    let bayer = Dither::Bayer {
+        order: 2,
+        spread: 1.0,
+    };
+    assert!((mean(bayer) - 0.25).abs() < 0.01);
+    // error diffusion loses the errors at the edges of the image
+    assert!((mean(Dither::FloydSteinberg) - 0.25).abs() < 0.02);
+    assert!((mean(Dither::JarvisJudiceNinke) - 0.25).abs() < 0.02);
+    // and atkinson loses a quarter of every error, which makes midtones darker
+    let atkinson = mean(Dither::Atkinson);
+    assert!(atkinson > 0.1 && atkinson < 0.25);
+
+    // the bayer pattern is regular
+    let indices = dither(&image, 32, &palette, bayer, &LinearDistance);
+    assert_eq!(indices[..4], indices[4..8]);
+    assert_eq!(indices[..32], indices[4 * 32..5 * 32]);
+
+    // too large orders are clamped instead of allocating a huge matrix
+    let bayer = |order| Dither::Bayer { order, spread: 1.0 };
+    assert_eq!(
+        dither(&image, 32, &palette, bayer(u32::MAX), &LinearDistance),
+        dither(
+            &image,
+            32,
+            &palette,
+            bayer(MAX_BAYER_ORDER),
+            &LinearDistance
+        )
+    );
+
+    assert!(dither(&image, 32, &[], Dither::FloydSteinberg, &LinearDistance).is_empty());
+}
+
+#[test]
+fn slice_casting() {
+    let mut channels = [255u8, 0, 0, 0, 128, 255];
+    let colors = SRGB24Color::from_channels(&channels).unwrap();
+    assert_eq!(colors, &[(255, 0, 0).into(), (0, 128, 255).into()]);
+    assert_eq!(SRGB24Color::as_channels(colors), &channels);
+
+    let colors = SRGB24Color::from_channels_mut(&mut channels).unwrap();
+    colors[1].g = 64;
+    SRGB24Color::as_channels_mut(colors)[0] = 10;
+    assert_eq!(channels, [10, 0, 0, 0, 64, 255]);
+
+    let error = SRGB24Color::from_channels(&channels[1..]).unwrap_err();
+    assert_eq!(
+        error,
+        CastError {
+            len: 5,
+            channels: 3
+        }
+    );
+    assert_eq!(
+        SRGBA32Color::from_channels(&channels[..5]).unwrap_err(),
+        CastError {
+            len: 5,
+            channels: 4
+        }
+    );
+
+    let channels = [0.5f32, 0.25, 1.0, 0.75];
+    let colors = LinRGBAColor::from_channels(&channels).unwrap();
+    assert_eq!(colors, &[LinRGBAColor::new((0.5, 0.25, 1.0), 0.75)]);
+    assert!(LinRGBColor::from_channels(&[]).unwrap().is_empty());
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn bytemuck_casting() {
+    let colors = [LinRGB48Color::from((1, 2, 3)), (4, 5, 6).into()];
+    let bytes: &[u8] = bytemuck::cast_slice(&colors);
+    assert_eq!(bytes.len(), 12);
+    let back: &[LinRGB48Color] = bytemuck::cast_slice(bytes);
+    assert_eq!(back, &colors);
+
+    let pixel: SRGBA32Color = bytemuck::cast(0x0403_0201u32.to_le_bytes());
+    assert_eq!(pixel, SRGBA32Color::new((1, 2, 3), 4));
+}
+
+#[test]
+fn batch_conversions() {
+    use crate::batch::*;
+
+    let channels: Vec<u8> = (0..=255).chain(0..=255).chain(0..=255).collect();
+    let srgb24 = SRGB24Color::from_channels(&channels).unwrap();
+    let mut linear = vec![LinRGBColor::default(); srgb24.len()];
+    decode_srgb24(srgb24, &mut linear);
+    for (s, l) in srgb24.iter().zip(&linear) {
+        assert_eq!(*l, s.conv::<f32>().std_decode());
+    }
+
+    let mut encoded = vec![SRGB24Color::default(); linear.len()];
+    encode_srgb24(&linear, &mut encoded);
+    assert_eq!(encoded, srgb24);
+
+    let srgb48: Vec<RGBColor<u16, SRGBSpace>> = (0..=u16::MAX)
+        .step_by(7)
+        .map(|x| (x, x / 2, !x).into())
+        .collect();
+    let mut linear = vec![LinRGBColor::default(); srgb48.len()];
+    decode_srgb48(&srgb48, &mut linear);
+    for (s, l) in srgb48.iter().zip(&linear) {
+        assert_eq!(*l, s.conv::<f32>().std_decode());
+    }
+
+    let srgb: Vec<SRGBColor> = (0..=1000)
+        .map(|i| {
+            let x = i as f32 / 1000.0;
+            (x, 1.0 - x, (x * 7.0) % 1.0).into()
+        })
+        .collect();
+    let mut linear = vec![LinRGBColor::default(); srgb.len()];
+    decode_srgb(&srgb, &mut linear);
+    let mut back = vec![SRGBColor::default(); srgb.len()];
+    encode_srgb(&linear, &mut back);
+    for ((s, l), b) in srgb.iter().zip(&linear).zip(&back) {
+        assert_eq!(*l, s.std_decode());
+        assert_eq!(*b, l.std_encode());
+    }
+
+    let mut hsv = vec![StdHSVColor::default(); srgb.len()];
+    rgb_to_hsv(&srgb, &mut hsv);
+    let mut rgb = vec![SRGBColor::default(); srgb.len()];
+    hsv_to_rgb(&hsv, &mut rgb);
+    for ((s, h), r) in srgb.iter().zip(&hsv).zip(&rgb) {
+        assert_eq!(*h, s.hsv());
+        assert_eq!(*r, h.rgb());
+    }
+
+    let mut floats = vec![0.0; channels.len()];
+    u8_to_f32(&channels, &mut floats);
+    let mut bytes = vec![0; channels.len()];
+    f32_to_u8(&floats, &mut bytes);
+    for ((c, f), b) in channels.iter().zip(&floats).zip(&bytes) {
+        assert_eq!(*f, c.conv::<f32>());
+        assert_eq!(*b, f.conv::<u8>());
+    }
+    assert_eq!(bytes, channels);
+}
+
+#[test]
+fn lookup_table_gamma() {
+    use crate::space::*;
+
+    for x in 0..=255u8 {
+        assert_eq!(std_gamma_decode_u8(x), std_gamma_decode(x.conv::<f32>()));
+    }
+    for x in (0..=u16::MAX).step_by(3) {
+        assert_eq!(std_gamma_decode_u16(x), std_gamma_decode(x.conv::<f32>()));
+    }
+
+    // checking every float between 0 and 1 takes too long, so check a sample of them
+    let float_path = |x: f32| std_gamma_encode(x).conv::<u8>();
+    for bits in (0..=1.0f32.to_bits()).step_by(997) {
+        let x = f32::from_bits(bits);
+        assert_eq!(std_gamma_encode_u8(x), float_path(x), "{:e}", x);
+    }
+    // the exact thresholds between the encoded values
+    for k in 1..=255u8 {
+        let x = std_gamma_decode((k as f32 - 0.5) / 255.0);
+        for x in [
+            x,
+            f32::from_bits(x.to_bits() - 1),
+            f32::from_bits(x.to_bits() + 1),
+        ] {
+            assert_eq!(std_gamma_encode_u8(x), float_path(x), "{:e}", x);
+        }
+    }
+    assert_eq!(std_gamma_encode_u8(-1.0), 0);
+    assert_eq!(std_gamma_encode_u8(2.0), 255);
+    assert_eq!(std_gamma_encode_u8(f32::NAN), 0);
+
+    let color = SRGB24Color::from((12, 128, 250));
+    let linear = LinRGBColor::from(color);
+    assert_eq!(linear, color.conv::<f32>().std_decode());
+    assert_eq!(SRGB24Color::from(linear), color);
+    let color = RGBColor::<u16, SRGBSpace>::from((1000, 30000, 65535));
+    assert_eq!(LinRGBColor::from(color), color.conv::<f32>().std_decode());
+}
+
+#[test]
+fn channel_types() {
+    // u32 and f64 keep their precision
+    assert_eq!(u32::MAX.conv::<f64>(), 1.0);
+    assert_eq!((u32::MAX - 1).conv::<f64>().conv::<u32>(), u32::MAX - 1);
+    assert_eq!(0.1f64.conv::<u32>(), 429_496_730);
+    assert_eq!(0.123_456_789_012f64.conv::<f64>(), 0.123_456_789_012);
+    assert_eq!(1u8.conv::<f64>(), 1.0 / 255.0);
+    assert_eq!(0.5f64.conv::<u8>(), 128);
+    assert_eq!((2.0f64).conv::<f32>(), 1.0);
+
+    // the conversions with f32 and the small channels don't change
+    assert_eq!(1u8.conv::<f32>(), 1.0f32 / 255.0);
+    assert_eq!(u16::MAX.conv::<u8>(), 255);
+
+    // the f64 colors work like the f32 colors
+    let color: RGBColor<f64, SRGBSpace> = SRGB24Color::from((255, 128, 0)).conv();
+    assert_eq!(color.conv::<u8>(), (255, 128, 0).into());
+    let linear = color.decode();
+    assert!((linear.g - 0.215_860_500_113_899_26).abs() < 1e-12);
+
+    // signed channels are normalized to their maximum, and negative values are clamped
+    assert_eq!(i8::MAX.conv::<u8>(), 255);
+    assert_eq!((-5i8).conv::<f32>(), 0.0);
+    assert_eq!(1.0f32.conv::<i16>(), i16::MAX);
+    assert_eq!(0.5f32.conv::<i8>(), 64);
+    assert_eq!(
+        RGBColor::<i16, LinearSpace>::from((-1, 0, 1)).tuple(),
+        (0, 0, 1)
+    );
+
+    // fixed-point channels have 1.0 at a power of two
+    type Q12 = Fixed<u16, 12>;
+    assert_eq!(Q12::ch_max(), Fixed(4096));
+    assert_eq!(1.0f32.conv::<Q12>(), Fixed(4096));
+    assert_eq!(Fixed::<u16, 12>(2048).conv::<u8>(), 128);
+    assert_eq!(Channel::clamp(Fixed::<u16, 12>(5000)), Fixed(4096));
+    assert_eq!(Fixed::<u8, 7>(64).conv::<f32>(), 0.5);
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn f16_channels() {
+    use half::f16;
+
+    assert_eq!(f16::ch_max(), f16::ONE);
+    assert_eq!(255u8.conv::<f16>(), f16::ONE);
+    assert_eq!(f16::from_f32(0.5).conv::<u8>(), 128);
+    assert_eq!(f16::from_f32(2.0).conv::<f32>(), 1.0);
+
+    let color: RGBColor<f16, SRGBSpace> = SRGB24Color::from((0, 51, 255)).conv();
+    assert_eq!(color.conv::<u8>(), (0, 51, 255).into());
+}
+
+#[test]
+fn packed_colors() {
+    // expanding replicates the bits, so the extremes stay at the extremes
+    assert_eq!(SRGB24Color::from(RGB565(0xf800)), (255, 0, 0).into());
+    assert_eq!(
+        SRGB24Color::from(RGB565::new(16, 1, 3)),
+        (132, 4, 24).into()
+    );
+    assert_eq!(
+        RGB565::from(SRGB24Color::new(255, 128, 0)).fields(),
+        (31, 32, 0)
+    );
+    assert_eq!(RGB555::new(40, 1, 2).fields(), (31, 1, 2));
+
+    // every packed value survives unpacking and packing again
+    for packed in 0..=u16::MAX {
+        let rgb: SRGB24Color = RGB565(packed).into();
+        assert_eq!(RGB565::from(rgb), RGB565(packed));
+        let rgba: SRGBA32Color = RGBA4444(packed).into();
+        assert_eq!(RGBA4444::from(rgba), RGBA4444(packed));
+        let rgb: RGBColor<f32, SRGBSpace> = RGB555(packed & 0x7fff).into();
+        assert_eq!(RGB555::from(rgb), RGB555(packed & 0x7fff));
+    }
+    for value in 0..1024 {
+        let packed = RGB10A2::new(value, 1023 - value, value / 2, value % 4);
+        let rgba: Alpha<RGBColor<u16, LinearSpace>, u16> = packed.into();
+        assert_eq!(RGB10A2::from(rgba), packed);
+    }
+
+    let pixel = SRGBA32Color::new((0x11, 0x22, 0x33), 0x44);
+    assert_eq!(BGRA8::from(pixel), BGRA8(0x3322_1144));
+    assert_eq!(ARGB8::from(pixel), ARGB8(0x4411_2233));
+    assert_eq!(ABGR8::from(pixel), ABGR8(0x4433_2211));
+    assert_eq!(SRGBA32Color::from(ARGB8(0x4411_2233)), pixel);
+    assert_eq!(ABGR8::from(pixel).0.to_le_bytes(), [0x11, 0x22, 0x33, 0x44]);
+
+    let hdr = R11G11B10F::pack(1.0, 0.5, 1008.0);
+    assert_eq!(hdr.unpack(), (1.0, 0.5, 1008.0));
+    assert_eq!(LinRGBColor::from(hdr), (1.0, 0.5, 1.0).into());
+    assert_eq!(
+        R11G11B10F::pack(-1.0, 1e9, 0.0).unpack(),
+        (0.0, 65024.0, 0.0)
+    );
+    let (r, g, _) = R11G11B10F::pack(f32::INFINITY, f32::NAN, 0.0).unpack();
+    assert!(r == f32::INFINITY && g.is_nan());
+    // the mantissa is rounded to the nearest value, ties to even
+    assert_eq!(
+        R11G11B10F::pack(1.0 + 1.0 / 128.0, 1.0 + 3.0 / 128.0, 0.0)
+            .unpack()
+            .0,
+        1.0
+    );
+    assert_eq!(
+        R11G11B10F::pack(0.0, 1.0 + 3.0 / 128.0, 0.0).unpack().1,
+        1.0 + 4.0 / 128.0
+    );
+    // also in the subnormals, where the smallest step is 2^-20
+    let tiny = 2f32.powi(-20);
+    assert_eq!(R11G11B10F::pack(0.5 * tiny, 1.5 * tiny, 0.0).0, 2 << 11);
+    assert_eq!(R11G11B10F::pack(2.5 * tiny, 2.6 * tiny, 0.0).0, 2 | 3 << 11);
+    for bits in 0..0x7c0 {
+        let packed = R11G11B10F(bits | bits << 11 | (bits >> 1) << 22);
+        let (r, g, b) = packed.unpack();
+        assert_eq!(R11G11B10F::pack(r, g, b), packed);
+    }
+}
+
+#[test]
+fn channel_layouts() {
+    let buffer = [0x33u8, 0x22, 0x11, 0x66, 0x55, 0x44];
+    let pixels = Bgr::<u8, SRGBSpace>::from_channels(&buffer).unwrap();
+    let colors: Vec<SRGB24Color> = pixels.iter().map(|&p| p.into()).collect();
+    assert_eq!(
+        colors,
+        [(0x11, 0x22, 0x33).into(), (0x44, 0x55, 0x66).into()]
+    );
+    assert_eq!(Bgr::from(colors[0]), pixels[0]);
+
+    let buffer = [0x44u8, 0x11, 0x22, 0x33];
+    let argb = Argb::<u8, SRGBSpace>::from_channels(&buffer).unwrap()[0];
+    let rgba = SRGBA32Color::new((0x11, 0x22, 0x33), 0x44);
+    assert_eq!(SRGBA32Color::from(argb), rgba);
+    assert_eq!(argb.into_iter().collect::<Vec<_>>(), buffer);
+
+    let bgra: Bgra<u8, SRGBSpace> = rgba.into();
+    assert_eq!(bgra.array(), [0x33, 0x22, 0x11, 0x44]);
+    let abgr: Abgr<u8, SRGBSpace> = rgba.into();
+    assert_eq!(Abgr::as_channels(&[abgr]), [0x44, 0x33, 0x22, 0x11]);
+    assert_eq!(SRGBA32Color::from(abgr), rgba);
+
+    assert!(Bgra::<f32, LinearSpace>::from_channels(&[0.0; 6]).is_err());
+}
+
+#[test]
+fn angles() {
+    use std::f64::consts::PI;
+
+    let rad: Rad<f64> = Deg(90.0f64).conv();
+    assert!((rad.0 - PI / 2.0).abs() < 1e-15);
+    assert!((rad.sin() - 1.0).abs() < 1e-15);
+    assert!((Rad::atan2(-1.0f64, 0.0).0 - 1.5 * PI).abs() < 1e-15);
+    assert!((Rad::acos(0.0f32).conv::<Deg<f32>>().0 - 90.0).abs() < 1e-4);
+    assert_eq!(Rev(0.25f64).conv::<Deg<f64>>(), Deg(90.0));
+
+    assert_eq!(Deg(350.0f32).difference(Deg(10.0)), Deg(20.0));
+    assert_eq!(Deg(10i32).difference(Deg(200)), Deg(170));
+    assert!((Deg(350.0f64).signed_difference(Deg(10.0)) - 20.0 / 360.0).abs() < 1e-12);
+    assert!((Deg(350.0f64).lerp(Deg(10.0), 0.25).0 - 355.0).abs() < 1e-9);
+    assert!((Deg(10.0f64).lerp(Deg(350.0), 0.75).0 - 355.0).abs() < 1e-9);
+    assert_eq!(Rev(200u8).lerp(Rev(10), 0.5), Rev(233));
+
+    let mean = mean_angle(vec![Deg(350.0f64), Deg(10.0), Deg(0.0)]).unwrap();
+    assert!(mean.0.min(360.0 - mean.0) < 1e-9);
+    assert!((mean_angle(vec![Deg(80.0f64), Deg(100.0)]).unwrap().0 - 90.0).abs() < 1e-9);
+    assert_eq!(mean_angle(vec![Deg(90.0f64), Deg(270.0)]), None);
+    assert_eq!(mean_angle(Vec::<Deg<f64>>::new()), None);
+
+    // any hue can be converted without panicking
+    let red = SRGB24Color::new(255, 0, 0);
+    for hue in [-360.0f32, 720.0, 1e9, f32::NAN, f32::INFINITY] {
+        let mut hsv = HSVColor::<Rad, f32, SRGBSpace>::new(Rad(0.0), 1.0, 1.0);
+        hsv.h = Rad(hue);
+        let _ = hsv.rgb();
+    }
+    let hsv = HSVColor::<Rev<f32>, f32, SRGBSpace>::new(Rev(2.0), 1.0, 1.0);
+    assert_eq!(hsv.rgb().conv::<u8>(), red);
+    let hsv = HSVColor::<Deg<f64>, f64, SRGBSpace>::new(Deg(240.0), 1.0, 0.5);
+    assert_eq!(hsv.rgb().tuple(), (0.0, 0.0, 0.5));
+}
+
+#[test]
+fn circular_statistics() {
+    use crate::stats::*;
+
+    let hues = [(Deg(350.0f32), 1.0), (Deg(10.0), 1.0), (Deg(30.0), 2.0)];
+    let mean = weighted_mean_angle(hues.iter().copied()).unwrap();
+    assert!((mean.0 - 15.1).abs() < 0.1);
+    assert_eq!(circular_variance(vec![(Deg(90.0f32), 1.0); 3]), Some(0.0));
+    let spread = (0..4).map(|i| (Deg(i as f32 * 90.0), 1.0));
+    assert!(circular_variance(spread.clone()).unwrap() > 0.999);
+    assert_eq!(weighted_mean_angle(spread), None);
+    assert_eq!(weighted_mean_angle(vec![(Rad(1.0), 0.0)]), None);
+    // the weighted and unweighted means agree on nearly opposite angles
+    let opposite = [Deg(0.0f64), Deg(180.0 - 1e-7)];
+    assert_eq!(
+        weighted_mean_angle(opposite.iter().map(|&angle| (angle, 1.0))),
+        mean_angle(opposite.iter().copied())
+    );
+    assert_eq!(circular_variance(Vec::<(Rad, f32)>::new()), None);
+
+    let histogram = hue_histogram(hues.iter().copied(), 4);
+    assert_eq!(histogram, [0.75, 0.0, 0.0, 0.25]);
+    assert!(hue_histogram(hues.iter().copied(), 0).is_empty());
+
+    // greys don't affect the mean hue
+    let colors = vec![
+        StdHSVColor::new(350.0, 1.0, 1.0),
+        StdHSVColor::new(10.0, 1.0, 1.0),
+        StdHSVColor::new(0.0, 0.0, 0.5),
+        StdHSVColor::new(180.0, 0.1, 0.1),
+    ];
+    let mean = mean_hue(colors.clone()).unwrap();
+    assert!(mean.0.min(360.0 - mean.0) < 0.1);
+    assert!(hue_variance(colors.clone()).unwrap() < 0.05);
+    let histogram = color_hue_histogram(colors, 4);
+    assert_eq!(histogram[0], histogram[3]);
+    assert!(histogram[1] == 0.0 && histogram[2] < 0.01);
+    assert_eq!(mean_hue(vec![StdHSVColor::new(0.0, 0.0, 1.0)]), None);
+
+    let colors = [SRGB24Color::new(255, 0, 0), SRGB24Color::new(0, 0, 255)];
+    let linear: SRGB24Color = mean_color(colors.iter().copied(), MeanSpace::Linear).unwrap();
+    assert_eq!(linear, (188, 0, 188).into());
+    let oklab: SRGB24Color = mean_color(colors.iter().copied(), MeanSpace::Oklab).unwrap();
+    assert!(oklab.r < linear.r && oklab.b < linear.b);
+    assert_eq!(
+        mean_color(Vec::<SRGB24Color>::new(), MeanSpace::Oklab),
+        None
+    );
+}
+
+#[test]
+fn checked_constructors() {
+    assert_eq!(
+        SRGBColor::try_new(0.5, 1.0, 0.0),
+        Ok(SRGBColor::new(0.5, 1.0, 0.0))
+    );
+    let error = LinRGBColor::try_new(0.5, 1.7, 2.0).unwrap_err();
+    assert_eq!(
+        error,
+        ChannelRangeError {
+            channel: "green",
+            value: 1.7f32 as f64
+        }
+    );
+    assert!(error
+        .to_string()
+        .starts_with("the green channel is out of range: 1.7"));
+    assert_eq!(
+        LinRGBColor::try_new(0.0, 0.0, f32::NAN)
+            .unwrap_err()
+            .channel,
+        "blue"
+    );
+
+    let hdr = LinRGBColor::new_unchecked(1.7, -0.5, 0.0);
+    assert_eq!(hdr.tuple(), (1.7, -0.5, 0.0));
+    assert_eq!(LinRGBColor::new(1.7, -0.5, 0.0).tuple(), (1.0, 0.0, 0.0));
+
+    assert_eq!(
+        StdHSVColor::try_new(Deg(400.0), 0.5, 0.5).unwrap_err(),
+        ChannelRangeError {
+            channel: "hue",
+            value: 400.0
+        }
+    );
+    assert_eq!(
+        StdHSVColor::try_new(Deg(120.0), 1.5, 0.5)
+            .unwrap_err()
+            .channel,
+        "saturation"
+    );
+    assert_eq!(
+        StdHSVColor::try_new(Deg(120.0), 0.0, 0.5),
+        Ok(StdHSVColor::new(0.0, 0.0, 0.5))
+    );
+    assert_eq!(
+        StdHSVColor::new_unchecked(Deg(400.0), 0.5, 2.0).h,
+        Deg(400.0)
+    );
+
+    let color = SRGB24Color::new(1, 2, 3);
+    assert_eq!(
+        SRGBAColor::try_new(color.conv(), 1.2).unwrap_err(),
+        ChannelRangeError {
+            channel: "alpha",
+            value: 1.2f32 as f64
+        }
+    );
+    assert_eq!(
+        SRGBA32Color::try_new(color, 255),
+        Ok(SRGBA32Color::new(color, 255))
+    );
+    assert_eq!(SRGBAColor::new_unchecked(color.conv(), 1.2).alpha, 1.2);
+}
+
+#[test]
+fn tone_mapping() {
+    let hdr = HDRColor::new(4.0f32, 1.0, -1.0);
+    assert_eq!(hdr.tuple(), (4.0, 1.0, 0.0));
+    assert_eq!(hdr.normalize(), hdr);
+    assert_eq!(hdr.exposure(1.0).tuple(), (8.0, 2.0, 0.0));
+    assert_eq!(hdr.exposure(-2.0).tuple(), (1.0, 0.25, 0.0));
+    assert!((HDRColor::new(2.0f32, 2.0, 2.0).luminance() - 2.0).abs() < 1e-6);
+    assert_eq!(hdr.to_linear(), (1.0, 1.0, 0.0).into());
+
+    assert_eq!(hdr.tone_map(ToneMap::Reinhard).tuple(), (0.8, 0.5, 0.0));
+    let extended = hdr.tone_map(ToneMap::ReinhardExtended { white: 4.0 });
+    assert_eq!(extended.r, 1.0);
+    for &white in &[0.0, -1.0, f32::NAN] {
+        let mapped = hdr.tone_map(ToneMap::ReinhardExtended { white });
+        assert!(mapped.is_normal() && mapped.b == 0.0, "{:?}", mapped);
+    }
+    assert!((HDRColor::new(5.6f32, 0.0, 0.0).tone_map(ToneMap::Hable).r - 1.0).abs() < 1e-5);
+
+    let operators = [
+        ToneMap::Reinhard,
+        ToneMap::ReinhardExtended { white: 8.0 },
+        ToneMap::AcesFilmic,
+        ToneMap::Hable,
+        ToneMap::AgX,
+    ];
+    for &operator in &operators {
+        // the curves start from black, never decrease and stay in the range
+        let mut previous = -1.0;
+        for i in 0..200 {
+            let x = (i as f32 * 0.1 - 10.0).exp2();
+            let mapped = HDRColor::new(x, x, x).tone_map(operator);
+            assert!(mapped.r >= previous, "{:?} at {}", operator, x);
+            assert!((mapped.r - mapped.b).abs() < 1e-3);
+            previous = mapped.r;
+        }
+        let black = HDRColor::new(0.0f32, 0.0, 0.0).tone_map(operator);
+        assert!(black.r < 0.01, "{:?}", operator);
+        let white = HDRColor::new(1e4f32, 1e4, 1e4).to_srgb(operator);
+        assert!(white.r > 0.95, "{:?}", operator);
+    }
+
+    // AgX keeps the middle grey close to the scene value
+    let grey = HDRColor::new(0.18f32, 0.18, 0.18).tone_map(ToneMap::AgX);
+    assert!((grey.g - 0.18).abs() < 0.05, "{}", grey.g);
+}
+
+#[test]
+fn hdr_transfer_functions() {
+    use crate::space::*;
+
+    assert!((nits_to_pq(100.0f64) - 0.508_078).abs() < 1e-5);
+    assert!((nits_to_pq(1000.0f64) - 0.751_827).abs() < 1e-5);
+    assert!((nits_to_pq(10000.0f32) - 1.0).abs() < 1e-6);
+    assert!(nits_to_pq(0.0f32) < 1e-5);
+    for &nits in &[0.01f64, 1.0, 203.0, 4000.0, 10000.0] {
+        assert!((pq_to_nits(nits_to_pq(nits)) - nits).abs() < nits * 1e-9);
+    }
+
+    assert!((hlg_encode(1.0f64 / 12.0) - 0.5).abs() < 1e-12);
+    assert!((hlg_encode(1.0f64) - 1.0).abs() < 1e-6);
+    for i in 0..=100 {
+        let x = i as f64 / 100.0;
+        assert!((hlg_decode(hlg_encode(x)) - x).abs() < 1e-12);
+    }
+    // the reference white is shown at about 203 nits on a 1000 nit display
+    assert!((hlg_to_nits(0.75f64, 1000.0) - REFERENCE_WHITE_NITS as f64).abs() < 1.0);
+    assert!((nits_to_hlg(hlg_to_nits(0.6f64, 2000.0), 2000.0) - 0.6).abs() < 1e-12);
+
+    let hdr = HDRColor::new(2.0f64, 0.5, 0.1);
+    let pq = RGBColor::<f64, Rec2100PQSpace>::from_hdr(hdr, 203.0);
+    let back = pq.to_hdr(203.0);
+    assert!((back.r - 2.0).abs() < 1e-9 && (back.g - 0.5).abs() < 1e-9);
+    // white has the same luminance in both primaries
+    let white = RGBColor::<f32, Rec2100PQSpace>::from_hdr(HDRColor::new(1.0, 1.0, 1.0), 203.0);
+    assert!((white.r - white.b).abs() < 1e-5 && (pq_to_nits(white.g) - 203.0).abs() < 0.01);
+
+    let hlg = RGBColor::<f64, Rec2100HLGSpace>::from_hdr(hdr);
+    let back = hlg.to_hdr();
+    assert!((back.r - 2.0).abs() < 1e-9 && (back.b - 0.1).abs() < 1e-9);
+    // the signal clips at about 3.8 times the reference white
+    let grey = |x: f64| RGBColor::<f64, Rec2100HLGSpace>::from_hdr(HDRColor::new(x, x, x)).g;
+    assert!(grey(3.7) < 1.0 && grey(3.8) == 1.0);
+    let white = RGBColor::<f32, Rec2100HLGSpace>::from_linear(BaseColor::White.into());
+    assert!((white.g - 0.75).abs() < 1e-5);
+    assert_eq!(
+        SRGB24Color::from_linear(white.to_linear()),
+        BaseColor::White.into()
+    );
+}
+
+#[test]
+fn video_encodings() {
+    use YCbCrMatrix::*;
+    use YCbCrRange::*;
+
+    let ycbcr = |color: SRGB24Color, matrix, range, bits| {
+        YCbCrColor::<f64>::from_rgb(color, matrix).to_ints(range, bits)
+    };
+    let (white, black) = (SRGB24Color::new(255, 255, 255), SRGB24Color::new(0, 0, 0));
+    let red = SRGB24Color::new(255, 0, 0);
+    assert_eq!(ycbcr(white, Bt709, Limited, 8), (235, 128, 128));
+    assert_eq!(ycbcr(black, Bt709, Limited, 8), (16, 128, 128));
+    assert_eq!(ycbcr(white, Bt2020, Limited, 10), (940, 512, 512));
+    assert_eq!(ycbcr(black, Bt2020, Limited, 12), (256, 2048, 2048));
+    assert_eq!(ycbcr(red, Bt709, Limited, 8), (63, 102, 240));
+    assert_eq!(ycbcr(red, Bt601, Limited, 8), (81, 90, 240));
+    assert_eq!(ycbcr(red, Bt601, Full, 8), (76, 85, 255));
+    assert_eq!(
+        ycbcr(SRGB24Color::new(0, 0, 255), Bt2020, Full, 10),
+        (61, 1023, 471)
+    );
+
+    // every 8-bit color survives the full range round trip at 10 bits
+    for &matrix in &[Bt601, Bt709, Bt2020] {
+        for i in 0..4096u32 {
+            let color = SRGB24Color::new((i * 7) as u8, ((i * 13) >> 2) as u8, (i >> 4) as u8);
+            let (y, cb, cr) = ycbcr(color, matrix, Full, 10);
+            let back = YCbCrColor::<f32>::from_ints(y, cb, cr, Full, 10).rgb(matrix);
+            assert_eq!(color, back);
+        }
+    }
+    let decoded: SRGB24Color = YCbCrColor::<f32>::from_ints(235, 128, 128, Limited, 8).rgb(Bt709);
+    assert_eq!(decoded, white);
+    let decoded: SRGB24Color = YCbCrColor::<f32>::from_ints(63, 102, 240, Limited, 8).rgb(Bt709);
+    assert!(decoded.r == 255 && decoded.g <= 1 && decoded.b <= 1);
+    let clamped = YCbCrColor::<f32>::from_ints(0, 0, 0, Limited, 8);
+    assert_eq!(clamped.tuple(), (0.0, -0.5, -0.5));
+
+    let color = SRGBColor::new(0.8, 0.4, 0.2);
+    let ycocg = YCoCgColor::from_rgb(color);
+    assert!((ycocg.y - 0.45).abs() < 1e-6);
+    assert!((ycocg.co - 0.3).abs() < 1e-6 && (ycocg.cg + 0.05).abs() < 1e-6);
+    let back: SRGBColor = ycocg.rgb();
+    assert!((back.r - 0.8).abs() < 1e-6 && (back.b - 0.2).abs() < 1e-6);
+    assert!(ycocg.is_normal() && ycocg.array() == [ycocg.y, ycocg.co, ycocg.cg]);
+    let out = YCoCgColor {
+        y: 1.5,
+        co: -0.75,
+        cg: 0.25,
+    }
+    .normalize();
+    assert_eq!(out.tuple(), (1.0, -0.5, 0.25));
+
+    for i in 0..=0xffffu32 {
+        let color = SRGB24Color::new(i as u8, (i >> 8) as u8, ((i * 31) >> 3) as u8);
+        let ycocg = YCoCgRColor::from(color);
+        assert!((0..=255).contains(&ycocg.y));
+        assert_eq!(SRGB24Color::from(ycocg), color);
+    }
+    let ycocg = YCoCgRColor::from(SRGB24Color::new(255, 0, 0));
+    assert_eq!((ycocg.y, ycocg.co, ycocg.cg), (63, 255, -127));
+
+    // greys have no chroma and the PQ encoded luminance as the intensity
+    let grey = ICtCpColor::from_hdr(HDRColor::new(1.0f64, 1.0, 1.0), 100.0);
+    assert!((grey.i - 0.508_078).abs() < 1e-5);
+    assert!(grey.ct.abs() < 1e-9 && grey.cp.abs() < 1e-9);
+    let hdr = HDRColor::new(4.0f64, 0.25, 0.01);
+    let ictcp = ICtCpColor::from_hdr(hdr, 203.0);
+    assert!(ictcp.ct < 0.0 && ictcp.cp > 0.0);
+    assert!(ictcp.is_normal() && !ICtCpColor { i: -0.1, ..ictcp }.is_normal());
+    assert_eq!(ICtCpColor { cp: 0.9, ..ictcp }.normalize().array()[2], 0.5);
+    let back = ictcp.to_hdr(203.0);
+    assert!((back.r - 4.0).abs() < 1e-9 && (back.g - 0.25).abs() < 1e-9);
+    assert!((back.b - 0.01).abs() < 1e-9);
+}
+
+#[test]
+fn subtractive_colors() {
+    let orange = SRGB24Color::new(255, 128, 0);
+    let cmy = CMYColor::from(orange);
+    assert_eq!(cmy.tuple(), (0, 127, 255));
+    assert_eq!(SRGB24Color::from(cmy), orange);
+
+    let color = SRGBColor::new(0.6, 0.4, 0.2);
+    let cmyk = CMYKColor::from(color);
+    assert!((cmyk.k - 0.4).abs() < 1e-6);
+    assert!(cmyk.c.abs() < 1e-6);
+    assert!((cmyk.m - 1.0 / 3.0).abs() < 1e-6 && (cmyk.y - 2.0 / 3.0).abs() < 1e-6);
+
+    // full removal gives back the same color with any amount of black
+    for &black in &[0.0, 0.3, 1.0] {
+        let generation = BlackGeneration::new(black, 1.0);
+        for i in 0..4096u32 {
+            let rgb = SRGB24Color::new((i * 5) as u8, (i >> 4) as u8, ((i * 11) >> 3) as u8);
+            let cmyk = CMYKColor::from_rgb(rgb.conv::<f32>(), generation);
+            assert_eq!(cmyk.rgb::<SRGBSpace>().conv::<u8>(), rgb);
+        }
+    }
+    let none = CMYKColor::from_rgb(color, BlackGeneration::NONE);
+    assert_eq!(none.k, 0.0);
+    assert_eq!(none.cmy(), CMYColor::from(color));
+
+    // without removal the colored inks stay under the black and the color gets darker
+    let rich = CMYKColor::from_rgb(color, BlackGeneration::new(1.0, 0.0));
+    assert!((rich.c - 0.4).abs() < 1e-6 && (rich.k - 0.4).abs() < 1e-6);
+    assert!(rich.rgb::<SRGBSpace>().r < 0.6);
+
+    let black = CMYKColor::new(0.5, 0.2, 1.5, 1.0);
+    assert_eq!(black.tuple(), (0.0, 0.0, 0.0, 1.0));
+    assert!(black.is_normal() && !CMYKColor { k: 1.0, ..cmyk }.is_normal());
+    assert_eq!(CMYKColor::from(SRGBColor::new(0.0, 0.0, 0.0)), black);
+    assert_eq!(CMYColor::new(-0.5, 0.5, 2.0).tuple(), (0.0, 0.5, 1.0));
+
+    assert_eq!(format!("{}", cmy), "  0.0%, 49.8%,100.0%");
+    assert_eq!(
+        format!("{}", CMYKColor::new(0.0, 0.25, 1.0, 0.125)),
+        "  0.0%, 25.0%,100.0%, 12.5%"
+    );
+}
@@ -0,0 +1,149 @@
+use num_traits::Float;
+
+use crate::*;
+
+/// A color in the CIE 1931 XYZ color space
+///
+/// The colors are relative to the D65 white point of sRGB, so that the white of the linear RGB
+/// space has the luminance `y` of 1. The channels can't be negative, but they don't have an
+/// upper limit.
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq)]
+pub struct XYZColor<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+type Matrix = [[f32; 3]; 3];
+
+const RGB_TO_XYZ: Matrix = [
+    [0.412_456_4, 0.357_576_1, 0.180_437_5],
+    [0.212_672_9, 0.715_152_2, 0.072_175],
+    [0.019_333_9, 0.119_192, 0.950_304_1],
+];
+
+const XYZ_TO_RGB: Matrix = [
+    [3.240_454_2, -1.537_138_5, -0.498_531_4],
+    [-0.969_266, 1.876_010_8, 0.041_556],
+    [0.055_643_4, -0.204_025_9, 1.057_225_2],
+];
+
+fn transform<T: Float>(m: &Matrix, (x, y, z): (T, T, T)) -> (T, T, T) {
+    let row = |i: usize| cuwf::<T>(m[i][0]) * x + cuwf::<T>(m[i][1]) * y + cuwf::<T>(m[i][2]) * z;
+    (row(0), row(1), row(2))
+}
+
+impl<T> XYZColor<T> {
+    /// Deconstructs this color into a tuple of it's channels
+    #[inline]
+    pub fn tuple(self) -> (T, T, T) {
+        (self.x, self.y, self.z)
+    }
+}
+
+impl<T: Float> XYZColor<T> {
+    /// Creates a new XYZ color
+    ///
+    /// Negative values are clamped to zero.
+    pub fn new(x: T, y: T, z: T) -> Self {
+        XYZColor { x, y, z }.normalize()
+    }
+
+    /// Creates a new XYZ color from the CIE xy chromaticity coordinates and the luminance
+    pub fn from_chromaticity(x: T, y: T, luminance: T) -> Self {
+        if y == T::zero() {
+            return XYZColor::new(T::zero(), T::zero(), T::zero());
+        }
+        let scale = luminance / y;
+        XYZColor::new(x * scale, luminance, (T::one() - x - y) * scale)
+    }
+
+    /// Returns the CIE xy chromaticity coordinates of this color
+    ///
+    /// Black has the chromaticity of the D65 white point.
+    pub fn chromaticity(&self) -> (T, T) {
+        let sum = self.x + self.y + self.z;
+        if sum == T::zero() {
+            return (cuwf(0.312_727), cuwf(0.329_023));
+        }
+        (self.x / sum, self.y / sum)
+    }
+
+    /// Transforms this color into linear RGB
+    ///
+    /// Colors outside of the RGB gamut are clamped into it.
+    pub fn rgb(self) -> RGBColor<T, LinearSpace>
+    where
+        T: Channel,
+    {
+        self.rgb_unclamped().into()
+    }
+
+    /// Returns the linear RGB channels of this color without clamping them
+    pub(crate) fn rgb_unclamped(self) -> (T, T, T) {
+        transform(&XYZ_TO_RGB, self.tuple())
+    }
+}
+
+impl<T: Float + Channel> RGBColor<T, LinearSpace> {
+    /// Transforms this color into the CIE XYZ space
+    pub fn xyz(self) -> XYZColor<T> {
+        let (x, y, z) = transform(&RGB_TO_XYZ, self.tuple());
+        XYZColor::new(x, y, z)
+    }
+}
+
+impl<T: Float> Color for XYZColor<T> {
+    /// Clamps the negative channels to zero
+    fn normalize(self) -> Self {
+        let f = |x: T| x.max(T::zero());
+        XYZColor {
+            x: f(self.x),
+            y: f(self.y),
+            z: f(self.z),
+        }
+    }
+
+    fn is_normal(&self) -> bool {
+        self.x >= T::zero() && self.y >= T::zero() && self.z >= T::zero()
+    }
+}
+
+impl<T: Float + Channel> ToLinear for XYZColor<T> {
+    #[inline]
+    fn to_linear(&self) -> LinRGBColor {
+        self.rgb().conv()
+    }
+}
+
+impl<T: Float + Channel> FromLinear for XYZColor<T> {
+    #[inline]
+    fn from_linear(color: LinRGBColor) -> Self {
+        color.conv::<T>().xyz()
+    }
+}
+
+impl<T: Float + Channel> From<XYZColor<T>> for RGBColor<T, LinearSpace> {
+    fn from(color: XYZColor<T>) -> Self {
+        color.rgb()
+    }
+}
+
+impl<T: Float + Channel> From<RGBColor<T, LinearSpace>> for XYZColor<T> {
+    fn from(color: RGBColor<T, LinearSpace>) -> Self {
+        color.xyz()
+    }
+}
+
+impl<T: Float + Channel> From<BaseColor> for XYZColor<T> {
+    fn from(base_color: BaseColor) -> Self {
+        RGBColor::<T, LinearSpace>::from(base_color).xyz()
+    }
+}
+
+impl<T: Float> From<(T, T, T)> for XYZColor<T> {
+    fn from(tuple: (T, T, T)) -> Self {
+        let (x, y, z) = tuple;
+        XYZColor::new(x, y, z)
+    }
+}
@@ -0,0 +1,347 @@
+use num_traits::Float;
+
+use crate::hdr::{transform, Matrix, REC2020_TO_REC709, REC709_TO_REC2020};
+use crate::space::{pq_decode, pq_encode, PQ_MAX_NITS};
+use crate::*;
+
+/// The luma coefficients of the video standards
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum YCbCrMatrix {
+    /// ITU-R BT.601, used by standard definition video and JPEG
+    Bt601,
+    /// ITU-R BT.709, used by high definition video
+    Bt709,
+    /// ITU-R BT.2020, used by ultra high definition video
+    Bt2020,
+}
+
+impl YCbCrMatrix {
+    /// Returns the weights of red and blue in the luma
+    ///
+    /// The weight of green is one minus these.
+    pub fn coefficients(self) -> (f64, f64) {
+        match self {
+            YCbCrMatrix::Bt601 => (0.299, 0.114),
+            YCbCrMatrix::Bt709 => (0.2126, 0.0722),
+            YCbCrMatrix::Bt2020 => (0.2627, 0.0593),
+        }
+    }
+}
+
+/// The range of the integer values of YCbCr colors
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum YCbCrRange {
+    /// All the values are used, like in JPEG
+    Full,
+    /// The limited range of video, also called studio swing
+    ///
+    /// With 8 bits the luma is between 16 and 235 and the chroma between 16 and 240. The values
+    /// are scaled by the number of bits above 8.
+    Limited,
+}
+
+/// A color in the YCbCr space of digital video, also called YUV
+///
+/// The luma `y` is between 0 and 1, and the chroma channels `cb` and `cr` between -0.5 and 0.5.
+/// The color is computed from the encoded RGB channels, so it's in the same space as the RGB
+/// color it was converted from.
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq)]
+pub struct YCbCrColor<T> {
+    pub y: T,
+    pub cb: T,
+    pub cr: T,
+}
+
+impl<T: Float> YCbCrColor<T> {
+    /// Creates a new YCbCr color
+    ///
+    /// The channels are clamped to their ranges.
+    pub fn new(y: T, cb: T, cr: T) -> Self {
+        YCbCrColor { y, cb, cr }.normalize()
+    }
+
+    /// Converts an RGB color into YCbCr with the given coefficients
+    pub fn from_rgb<U: Channel, S>(color: RGBColor<U, S>, matrix: YCbCrMatrix) -> Self {
+        let (kr, kb) = matrix.coefficients();
+        let (r, g, b) = color.map(|x| x.conv::<f64>()).tuple();
+        let y = kr * r + (1.0 - kr - kb) * g + kb * b;
+        let cb = (b - y) / (2.0 * (1.0 - kb));
+        let cr = (r - y) / (2.0 * (1.0 - kr));
+        YCbCrColor::new(cuwd(y), cuwd(cb), cuwd(cr))
+    }
+
+    /// Converts this color into RGB with the given coefficients
+    ///
+    /// The colors outside of the RGB gamut are clamped into it.
+    pub fn rgb<U: Channel, S>(self, matrix: YCbCrMatrix) -> RGBColor<U, S> {
+        let (kr, kb) = matrix.coefficients();
+        let (y, cb, cr) = (cuwtd(self.y), cuwtd(self.cb), cuwtd(self.cr));
+        let r = y + 2.0 * (1.0 - kr) * cr;
+        let b = y + 2.0 * (1.0 - kb) * cb;
+        let g = (y - kr * r - kb * b) / (1.0 - kr - kb);
+        let conv = |x: f64| Channel::clamp(x).conv::<U>();
+        RGBColor::new(conv(r), conv(g), conv(b))
+    }
+
+    /// Quantizes this color into integers with the given range and number of bits
+    ///
+    /// # Panics
+    /// If `bits` isn't between 8 and 16.
+    pub fn to_ints(self, range: YCbCrRange, bits: u32) -> (u16, u16, u16) {
+        assert!((8..=16).contains(&bits), "unsupported bit depth {}", bits);
+        let max = ((1u32 << bits) - 1) as f64;
+        let (y, cb, cr) = (cuwtd(self.y), cuwtd(self.cb), cuwtd(self.cr));
+        let (y, cb, cr) = match range {
+            YCbCrRange::Full => {
+                let mid = (1u32 << (bits - 1)) as f64;
+                (y * max, cb * max + mid, cr * max + mid)
+            }
+            YCbCrRange::Limited => {
+                let scale = (1u32 << (bits - 8)) as f64;
+                let chroma = |c: f64| (224.0 * c + 128.0) * scale;
+                ((219.0 * y + 16.0) * scale, chroma(cb), chroma(cr))
+            }
+        };
+        let quantize = |x: f64| x.round().clamp(0.0, max) as u16;
+        (quantize(y), quantize(cb), quantize(cr))
+    }
+
+    /// Creates a color from integers with the given range and number of bits
+    ///
+    /// Values outside of the limited range are clamped.
+    ///
+    /// # Panics
+    /// If `bits` isn't between 8 and 16.
+    pub fn from_ints(y: u16, cb: u16, cr: u16, range: YCbCrRange, bits: u32) -> Self {
+        assert!((8..=16).contains(&bits), "unsupported bit depth {}", bits);
+        let (y, cb, cr) = (y as f64, cb as f64, cr as f64);
+        let (y, cb, cr) = match range {
+            YCbCrRange::Full => {
+                let max = ((1u32 << bits) - 1) as f64;
+                let mid = (1u32 << (bits - 1)) as f64;
+                (y / max, (cb - mid) / max, (cr - mid) / max)
+            }
+            YCbCrRange::Limited => {
+                let scale = (1u32 << (bits - 8)) as f64;
+                let chroma = |c: f64| (c / scale - 128.0) / 224.0;
+                ((y / scale - 16.0) / 219.0, chroma(cb), chroma(cr))
+            }
+        };
+        YCbCrColor::new(cuwd(y), cuwd(cb), cuwd(cr))
+    }
+}
+
+/// Clamps a luma or intensity channel between 0 and 1
+fn clamp_luma<T: Float>(x: T) -> T {
+    x.max(T::zero()).min(T::one())
+}
+
+/// Clamps a chroma channel between -0.5 and 0.5
+fn clamp_chroma<T: Float>(x: T) -> T {
+    let half = cuwf::<T>(0.5);
+    x.max(-half).min(half)
+}
+
+/// Implements the channel accessors and normalization of a color with a luma or intensity
+/// channel and two chroma channels
+macro_rules! impl_luma_chroma {
+    ( $( $name:ident { $luma:ident, $a:ident, $b:ident } ),* ) => { $(
+        impl<T> $name<T> {
+            /// Deconstructs this color into a tuple of it's channels
+            #[inline]
+            pub fn tuple(self) -> (T, T, T) {
+                (self.$luma, self.$a, self.$b)
+            }
+
+            /// Deconstructs this color into an array of it's channels
+            #[inline]
+            pub fn array(self) -> [T; 3] {
+                [self.$luma, self.$a, self.$b]
+            }
+        }
+
+        impl<T: Float> Color for $name<T> {
+            /// Clamps the channels to their ranges
+            fn normalize(self) -> Self {
+                $name {
+                    $luma: clamp_luma(self.$luma),
+                    $a: clamp_chroma(self.$a),
+                    $b: clamp_chroma(self.$b),
+                }
+            }
+
+            fn is_normal(&self) -> bool {
+                clamp_luma(self.$luma) == self.$luma
+                    && clamp_chroma(self.$a) == self.$a
+                    && clamp_chroma(self.$b) == self.$b
+            }
+        }
+    )* };
+}
+
+impl_luma_chroma!(
+    YCbCrColor { y, cb, cr },
+    YCoCgColor { y, co, cg },
+    ICtCpColor { i, ct, cp }
+);
+
+/// A color in the YCoCg space
+///
+/// The luma `y` is between 0 and 1, and the chroma channels `co` and `cg` between -0.5 and 0.5.
+/// The transform is cheaper than YCbCr and it decorrelates the channels of natural images well,
+/// so it's used by image compression.
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq)]
+pub struct YCoCgColor<T> {
+    pub y: T,
+    pub co: T,
+    pub cg: T,
+}
+
+impl<T: Float> YCoCgColor<T> {
+    /// Converts an RGB color into YCoCg
+    pub fn from_rgb<S>(color: RGBColor<T, S>) -> Self {
+        let (r, g, b) = color.tuple();
+        let (half, quarter) = (cuwf::<T>(0.5), cuwf::<T>(0.25));
+        YCoCgColor {
+            y: quarter * r + half * g + quarter * b,
+            co: half * (r - b),
+            cg: half * g - quarter * (r + b),
+        }
+    }
+
+    /// Converts this color into RGB
+    pub fn rgb<S>(self) -> RGBColor<T, S>
+    where
+        T: Channel,
+    {
+        let t = self.y - self.cg;
+        RGBColor::new(t + self.co, self.y + self.cg, t - self.co)
+    }
+}
+
+/// A color in the lossless YCoCg-R space
+///
+/// This is the reversible integer variant of YCoCg. The chroma channels need one bit more than
+/// the RGB channels, so an 8-bit color has the luma between 0 and 255 and the chroma between
+/// -255 and 255. Converting back gives exactly the original color.
+#[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct YCoCgRColor {
+    pub y: i16,
+    pub co: i16,
+    pub cg: i16,
+}
+
+impl YCoCgRColor {
+    /// Deconstructs this color into a tuple of it's channels
+    #[inline]
+    pub fn tuple(self) -> (i16, i16, i16) {
+        (self.y, self.co, self.cg)
+    }
+
+    /// Deconstructs this color into an array of it's channels
+    #[inline]
+    pub fn array(self) -> [i16; 3] {
+        [self.y, self.co, self.cg]
+    }
+}
+
+impl<S> From<RGBColor<u8, S>> for YCoCgRColor {
+    fn from(color: RGBColor<u8, S>) -> Self {
+        let (r, g, b) = color.map(|x| x as i16).tuple();
+        let co = r - b;
+        let t = b + (co >> 1);
+        let cg = g - t;
+        YCoCgRColor {
+            y: t + (cg >> 1),
+            co,
+            cg,
+        }
+    }
+}
+
+impl<S> From<YCoCgRColor> for RGBColor<u8, S> {
+    /// Converts the color back into RGB, clamping the values that no RGB color gives
+    fn from(color: YCoCgRColor) -> Self {
+        let t = color.y - (color.cg >> 1);
+        let g = color.cg + t;
+        let b = t - (color.co >> 1);
+        let r = b + color.co;
+        let clamp = |x: i16| Ord::clamp(x, 0, 255) as u8;
+        RGBColor::new(clamp(r), clamp(g), clamp(b))
+    }
+}
+
+/// A color in the ICtCp space of ITU-R BT.2100
+///
+/// ICtCp is used for HDR video. The intensity `i` is encoded with the Perceptual Quantizer, so
+/// it's between 0 and 1 for the luminances from 0 to 10000 nits. The chroma channels `ct` and
+/// `cp` are between -0.5 and 0.5.
+#[derive(Debug, Default, Copy, Clone, PartialOrd, PartialEq)]
+pub struct ICtCpColor<T> {
+    pub i: T,
+    pub ct: T,
+    pub cp: T,
+}
+
+const REC2020_TO_LMS: Matrix = [
+    [1688.0 / 4096.0, 2146.0 / 4096.0, 262.0 / 4096.0],
+    [683.0 / 4096.0, 2951.0 / 4096.0, 462.0 / 4096.0],
+    [99.0 / 4096.0, 309.0 / 4096.0, 3688.0 / 4096.0],
+];
+
+const LMS_TO_REC2020: Matrix = [
+    [
+        3.436_606_694_333_078,
+        -2.506_452_118_656_27,
+        0.069_845_424_323_191,
+    ],
+    [
+        -0.791_329_555_598_929,
+        1.983_600_451_792_291,
+        -0.192_270_896_193_362,
+    ],
+    [
+        -0.025_949_899_690_593,
+        -0.098_913_714_711_726,
+        1.124_863_614_402_319,
+    ],
+];
+
+const LMS_TO_ICTCP: Matrix = [
+    [0.5, 0.5, 0.0],
+    [6610.0 / 4096.0, -13613.0 / 4096.0, 7003.0 / 4096.0],
+    [17933.0 / 4096.0, -17390.0 / 4096.0, -543.0 / 4096.0],
+];
+
+const ICTCP_TO_LMS: Matrix = [
+    [1.0, 0.008_609_037_037_933, 0.111_029_625_003_026],
+    [1.0, -0.008_609_037_037_933, -0.111_029_625_003_026],
+    [1.0, 0.560_031_335_710_679, -0.320_627_174_987_319],
+];
+
+impl<T: Float> ICtCpColor<T> {
+    /// Converts an HDR color into ICtCp
+    ///
+    /// `white_nits` is the luminance of the color 1, usually `REFERENCE_WHITE_NITS`.
+    pub fn from_hdr(color: HDRColor<T>, white_nits: T) -> Self {
+        let scale = white_nits / cuwf(PQ_MAX_NITS);
+        let rgb = transform(&REC709_TO_REC2020, color.tuple());
+        let (l, m, s) = transform(&REC2020_TO_LMS, rgb);
+        let encode = |x: T| pq_encode(x * scale);
+        let (i, ct, cp) = transform(&LMS_TO_ICTCP, (encode(l), encode(m), encode(s)));
+        ICtCpColor { i, ct, cp }
+    }
+
+    /// Converts this color into an HDR color
+    ///
+    /// `white_nits` is the luminance that is converted into 1. The colors outside of the gamut
+    /// of sRGB are clamped into it.
+    pub fn to_hdr(self, white_nits: T) -> HDRColor<T> {
+        let scale = cuwf::<T>(PQ_MAX_NITS) / white_nits;
+        let (l, m, s) = transform(&ICTCP_TO_LMS, (self.i, self.ct, self.cp));
+        let decode = |x: T| pq_decode(x) * scale;
+        let rgb = transform(&LMS_TO_REC2020, (decode(l), decode(m), decode(s)));
+        let (r, g, b) = transform(&REC2020_TO_REC709, rgb);
+        HDRColor::new(r, g, b)
+    }
+}